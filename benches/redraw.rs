@@ -0,0 +1,116 @@
+//! Full screen redraws on large grids, every cell changes every frame. Runs the plugin headless
+//! without rendering, so it times the cell sync systems and not the GPU.
+//!
+//! cargo bench --bench redraw
+//!
+//! The target is a full redraw of a 400x120 grid (48000 cells) in under 16ms, a frame at 60Hz.
+//! The bench exits with an error when it is missed.
+
+use std::{
+    process::ExitCode,
+    time::{Duration, Instant},
+};
+
+use bevy::{
+    app::ScheduleRunnerPlugin, asset::AssetPlugin, hierarchy::HierarchyPlugin, input::InputPlugin,
+    prelude::*, transform::TransformPlugin, window::WindowPlugin,
+};
+use bevy_ratatui::{BevyBackend, RatatuiPlugin, TerminalComponent};
+use ratatui::{
+    style::{Color, Style},
+    widgets::{Block, Paragraph},
+    Terminal,
+};
+
+const FRAMES: u32 = 60;
+const TARGET: Duration = Duration::from_millis(16);
+
+fn main() -> ExitCode {
+    let mut missed = false;
+    for (width, height) in [(80, 24), (200, 60), (400, 120)] {
+        let per_frame = bench_grid(width, height);
+        let cells = width as u32 * height as u32;
+        let verdict = if (width, height) == (400, 120) {
+            if per_frame <= TARGET {
+                " (target met)"
+            } else {
+                missed = true;
+                " (target missed)"
+            }
+        } else {
+            ""
+        };
+        println!(
+            "{:>3}x{:<3} {:>6} cells  {:>8.2?} per full redraw{}",
+            width, height, cells, per_frame, verdict
+        );
+    }
+
+    if missed {
+        eprintln!("a full redraw of 400x120 took longer than {:?}", TARGET);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn bench_grid(width: u16, height: u16) -> Duration {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_once()),
+        AssetPlugin::default(),
+        WindowPlugin {
+            primary_window: None,
+            ..default()
+        },
+        InputPlugin,
+        HierarchyPlugin,
+        TransformPlugin,
+        RatatuiPlugin,
+    ))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlasLayout>()
+    .init_asset::<Font>();
+
+    let mut backend = BevyBackend::default();
+    backend.manual_window_sizing(true);
+    backend.resize(width, height);
+    app.world.spawn(TerminalComponent {
+        ratatui_terminal: Terminal::new(backend).unwrap(),
+    });
+
+    // lets the plugin spawn the cells
+    for _ in 0..5 {
+        app.update();
+    }
+
+    let mut total = Duration::ZERO;
+    for frame in 0..=FRAMES {
+        draw_frame(&mut app, frame);
+        let start = Instant::now();
+        app.update();
+        // the first frame warms up the queries
+        if frame > 0 {
+            total += start.elapsed();
+        }
+    }
+    total / FRAMES
+}
+
+/// Fills the whole grid with text that differs from the frame before in every cell.
+fn draw_frame(app: &mut App, frame: u32) {
+    let mut terminals = app.world.query::<&mut TerminalComponent>();
+    let mut termy = terminals.single_mut(&mut app.world);
+    let _ = termy.ratatui_terminal.draw(|f| {
+        let area = f.size();
+        let (fill, color) = [('a', Color::Red), ('b', Color::Blue)][frame as usize % 2];
+        let line = fill.to_string().repeat(area.width as usize);
+        let text = vec![line; area.height as usize].join("\n");
+        f.render_widget(
+            Paragraph::new(text)
+                .style(Style::default().fg(color))
+                .block(Block::default()),
+            area,
+        );
+    });
+}
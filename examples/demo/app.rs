@@ -1,6 +1,7 @@
 use rand::{
     distributions::{Distribution, Uniform},
-    rngs::ThreadRng,
+    rngs::StdRng,
+    SeedableRng,
 };
 use ratatui::widgets::*;
 
@@ -69,14 +70,14 @@ const EVENTS: [(&str, u64); 24] = [
 #[derive(Clone)]
 pub struct RandomSignal {
     distribution: Uniform<u64>,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl RandomSignal {
     pub fn new(lower: u64, upper: u64) -> RandomSignal {
         RandomSignal {
             distribution: Uniform::new(lower, upper),
-            rng: rand::thread_rng(),
+            rng: StdRng::from_entropy(),
         }
     }
 }
@@ -122,7 +123,7 @@ pub struct TabsState<'a> {
 }
 
 impl<'a> TabsState<'a> {
-    pub fn new(titles: Vec<&'a str>) -> TabsState {
+    pub fn new(titles: Vec<&'a str>) -> TabsState<'a> {
         TabsState { titles, index: 0 }
     }
     pub fn next(&mut self) {
@@ -1,38 +1,77 @@
 use std::{error::Error, time::Duration};
 
-use bevy::{
-    app::{App as BevyApp, AppExit},
-    prelude::*,
-};
+use bevy::{app::App as BevyApp, diagnostic::LogDiagnosticsPlugin, prelude::*};
 
-use bevy_ratatui::{BevyBackend, RatatuiPlugin, TerminalComponent};
+use bevy_ratatui::{
+    AppTerminal, BevyBackend, RatatuiApp, RatatuiAppExt, RatatuiPlugin, TermEvent, TermKey,
+    TerminalComponent, TerminalDiagnosticsPlugin,
+};
 
-use once_cell::sync::Lazy;
 use ratatui::prelude::*;
 
 use crate::{app::App as RatApp, ui};
 
-static mut RATAPP: Lazy<RatApp> = Lazy::new(|| RatApp::new("BEVY Demo", true));
+impl Resource for RatApp<'static> {}
 
-unsafe fn get_ratapp() -> &'static mut RatApp<'static> {
-    return &mut RATAPP;
+/// What the command line asked for, read when the app is created.
+#[derive(Resource)]
+struct DemoSettings {
+    enhanced_graphics: bool,
 }
 
-pub fn run(ticky_rate: Duration, enhanced_graphics: bool) -> Result<(), Box<dyn Error>> {
-    let ratapp = RatApp::new("Bevy Demo", enhanced_graphics);
+impl FromWorld for RatApp<'static> {
+    fn from_world(world: &mut World) -> Self {
+        let enhanced_graphics = world
+            .get_resource::<DemoSettings>()
+            .is_some_and(|settings| settings.enhanced_graphics);
+        RatApp::new("Bevy Demo", enhanced_graphics)
+    }
+}
+
+impl RatatuiApp for RatApp<'static> {
+    fn draw(&mut self, frame: &mut Frame) {
+        ui::draw(frame, self);
+    }
+
+    fn on_event(&mut self, event: TermEvent) {
+        let TermEvent::Key(key) = event else {
+            return;
+        };
+        match key.key {
+            TermKey::Char('h') | TermKey::Left => self.on_left(),
+            TermKey::Char('k') | TermKey::Up => self.on_up(),
+            TermKey::Char('l') | TermKey::Right => self.on_right(),
+            TermKey::Char('j') | TermKey::Down => self.on_down(),
+            TermKey::Char(c) => self.on_key(c),
+            _ => {}
+        }
+    }
 
-    let mut ra = unsafe { get_ratapp() };
-    *ra = ratapp;
+    fn on_tick(&mut self) {
+        RatApp::on_tick(self);
+    }
 
-    BevyApp::new()
-        .add_plugins(DefaultPlugins)
-        .add_plugins((RatatuiPlugin))
+    fn should_quit(&self) -> bool {
+        self.should_quit
+    }
+}
+
+pub fn run(
+    ticky_rate: Duration,
+    enhanced_graphics: bool,
+    diagnostics: bool,
+) -> Result<(), Box<dyn Error>> {
+    let mut app = BevyApp::new();
+    app.add_plugins(DefaultPlugins)
+        .add_plugins(RatatuiPlugin)
         .insert_resource(Time::<Fixed>::from_duration(ticky_rate))
-        .add_systems(Startup, camera_setup)
-        .add_systems(PreUpdate, terminal_draw)
-        .add_systems(FixedUpdate, app_tick)
-        .add_systems(Update, (keyboard_input))
-        .run();
+        .insert_resource(DemoSettings { enhanced_graphics })
+        .add_ratatui_app::<RatApp<'static>>()
+        .add_systems(Startup, camera_setup);
+    if diagnostics {
+        app.add_plugins((TerminalDiagnosticsPlugin, LogDiagnosticsPlugin::default()));
+    }
+    app.run();
 
     Ok(())
 }
@@ -51,53 +90,19 @@ fn camera_setup(mut commands: Commands) {
     ))
     .unwrap();
 
-    my_terminal.clear();
+    let _ = my_terminal.clear();
 
-    my_terminal.show_cursor();
+    let _ = my_terminal.show_cursor();
     // You can set manual_window_sizing to true if you dont want bevy_ratatui to manage window resizing for you
     // my_terminal.backend_mut().manual_window_sizing(true);
-
-    commands.spawn(TerminalComponent {
-        ratatui_terminal: my_terminal,
-    });
-}
-
-fn terminal_draw(mut terminal_query: Query<(&mut TerminalComponent)>) {
-    let mut ra = unsafe { get_ratapp() };
-    let mut rat_term = &mut terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend")
-        .ratatui_terminal;
-
-    let _ = rat_term.draw(|f| ui::draw(f, &mut ra));
-}
-
-fn app_tick() {
-    let mut ra = unsafe { get_ratapp() };
-    ra.on_tick();
-}
-
-fn keyboard_input(keys: Res<ButtonInput<KeyCode>>, mut exit: EventWriter<AppExit>) {
-    let ra = unsafe { get_ratapp() };
-    if keys.just_pressed(KeyCode::KeyQ) {
-        exit.send(AppExit);
-    }
-    if keys.just_pressed(KeyCode::KeyH) {
-        ra.on_left();
-    }
-    if keys.just_pressed(KeyCode::KeyK) {
-        ra.on_up();
-    }
-    if keys.just_pressed(KeyCode::KeyL) {
-        ra.on_right();
-    }
-    if keys.just_pressed(KeyCode::KeyJ) {
-        ra.on_down();
-    }
-    if keys.just_pressed(KeyCode::KeyC) {
-        ra.on_key("c".chars().next().unwrap());
-    }
-    if keys.just_pressed(KeyCode::KeyT) {
-        ra.on_key("t".chars().next().unwrap());
-    }
+    // or keep the 30x30 grid and scale the font to fill the window, letterboxing the rest
+    // my_terminal.backend_mut().sizing(bevy_ratatui::TerminalSizing::ScaleFont);
+
+    // the demo app draws into the terminal marked with its AppTerminal
+    commands.spawn((
+        TerminalComponent {
+            ratatui_terminal: my_terminal,
+        },
+        AppTerminal::<RatApp<'static>>::default(),
+    ));
 }
@@ -32,13 +32,14 @@ struct Cli {
     /// whether unicode symbols are used to improve the overall look of the app
     #[argh(option, default = "true")]
     enhanced_graphics: bool,
+    /// log what drawing the terminal costs every second
+    #[argh(switch)]
+    diagnostics: bool,
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let cli: Cli = argh::from_env();
     let tick_rate = Duration::from_millis(cli.tick_rate);
 
-    crate::bevy::run(tick_rate, cli.enhanced_graphics);
-
-    Ok(())
+    crate::bevy::run(tick_rate, cli.enhanced_graphics, cli.diagnostics)
 }
@@ -0,0 +1,98 @@
+// [Ratatui] Embedded terminal example
+
+use bevy::{app::AppExit, prelude::*};
+use ratatui::{
+    prelude::Terminal,
+    widgets::{Block, Paragraph, Wrap},
+};
+
+use bevy_ratatui::{BevyBackend, RatatuiPlugin, TerminalComponent};
+
+/// Places a ratatui terminal inside a Bevy UI flex layout, next to a native Bevy UI panel.
+/// The terminal grid follows the size of the node it is a child of, and the window is left alone.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RatatuiPlugin)
+        .add_systems(Startup, ui_setup)
+        .add_systems(PreUpdate, terminal_draw)
+        .add_systems(Update, keyboard_input)
+        .run();
+}
+
+fn ui_setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let mut backend = BevyBackend::default();
+    backend.embedded(true);
+    let my_terminal = Terminal::new(backend).unwrap();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            ..default()
+        })
+        .with_children(|root| {
+            // a native bevy ui sidebar
+            root.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(200.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                background_color: Color::MIDNIGHT_BLUE.into(),
+                ..default()
+            })
+            .with_children(|sidebar| {
+                sidebar.spawn(TextBundle::from_section(
+                    "Native Bevy UI",
+                    TextStyle {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                ));
+            });
+
+            // the terminal takes whatever space the flex layout gives this node
+            root.spawn(NodeBundle {
+                style: Style {
+                    flex_grow: 1.0,
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                ..default()
+            })
+            .with_children(|panel| {
+                panel.spawn(TerminalComponent {
+                    ratatui_terminal: my_terminal,
+                });
+            });
+        });
+}
+
+fn terminal_draw(mut terminal_query: Query<&mut TerminalComponent>) {
+    let rat_term = &mut terminal_query
+        .get_single_mut()
+        .expect("More than one terminal with a bevybackend")
+        .ratatui_terminal;
+
+    let _ = rat_term.draw(|frame| {
+        let area = frame.size();
+        frame.render_widget(
+            Paragraph::new("Resize the window, this panel follows the layout. (press 'q' to quit)")
+                .wrap(Wrap { trim: true })
+                .block(Block::bordered().title("Embedded terminal")),
+            area,
+        );
+    });
+}
+
+fn keyboard_input(keys: Res<ButtonInput<KeyCode>>, mut exit: EventWriter<AppExit>) {
+    if keys.just_pressed(KeyCode::KeyQ) {
+        exit.send(AppExit);
+    }
+}
@@ -1,23 +1,21 @@
 // [Ratatui] Hello Bevy example
 
-
 use bevy::{app::AppExit, prelude::*};
-use ratatui::{prelude::*, text::*, widgets::*};
+use ratatui::prelude::*;
 
-use bevy_ratatui::{BevyBackend, CellComponent, RatatuiPlugin, TerminalComponent};
+use bevy_ratatui::{BevyBackend, CellComponent, RatatuiContext, RatatuiPlugin, TerminalComponent};
 
 /// This is a bare minimum example. There are many approaches to running a bevy program, so
 /// this is not meant to be prescriptive. It is only meant to demonstrate the basic setup and
 /// teardown of a bevy ratatui terminal application.
-
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins((RatatuiPlugin))
+        .add_plugins(RatatuiPlugin)
         .add_systems(Startup, camera_and_terminal_setup)
         .add_systems(PreUpdate, terminal_draw)
-        .add_systems(Update, (keyboard_input))
-        .add_systems(Startup, (bevy_draw))
+        .add_systems(Update, keyboard_input)
+        .add_systems(Startup, bevy_draw)
         .run();
 }
 
@@ -44,7 +42,7 @@ fn camera_and_terminal_setup(mut commands: Commands) {
 
      */
 
-    my_terminal.clear();
+    let _ = my_terminal.clear();
 
     //Spawn entity with terminal component, you can then query for this entity to modify what is displayed, such as in terminal_draw
     commands.spawn(TerminalComponent {
@@ -52,14 +50,13 @@ fn camera_and_terminal_setup(mut commands: Commands) {
     });
 }
 
-fn terminal_draw(mut terminal_query: Query<(&mut TerminalComponent)>, mut commands: Commands) {
+fn terminal_draw(mut ratatui: RatatuiContext, mut commands: Commands) {
     let text = "Hello Bevy! From Ratatui with love. :D   (press 'q' to quit)   ";
 
-    // Standard terminal drawing by ratatui
-    let mut rat_term = &mut terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend")
-        .ratatui_terminal;
+    // Standard terminal drawing by ratatui, the terminal may not exist yet on the first frame
+    let Ok(mut rat_term) = ratatui.terminal() else {
+        return;
+    };
 
     let _ = rat_term.draw(|frame| {
         let area = frame.size();
@@ -68,10 +65,10 @@ fn terminal_draw(mut terminal_query: Query<(&mut TerminalComponent)>, mut comman
 
     // This hides UI nodes which would otherwise hide the sprite spawned by Bevy, note that normally you just want to set visibility to false,
     //but this is more performant
-    for (pos, e) in rat_term.backend().entity_map.iter() {
-        if pos.1 > 2 {
-            commands.entity(e.clone()).retain::<CellComponent>();
-        }
+    let termy_backend = rat_term.ratatui_terminal.backend();
+    let below_text = 3 * termy_backend.cell_columns as usize;
+    for e in termy_backend.cell_entities.iter().skip(below_text) {
+        commands.entity(*e).retain::<CellComponent>();
     }
 }
 
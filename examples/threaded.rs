@@ -0,0 +1,79 @@
+// [Ratatui] Threaded app example
+
+use std::{
+    io,
+    time::{Duration, Instant},
+};
+
+use bevy::{app::AppExit, prelude::*};
+use ratatui::{prelude::*, widgets::*};
+
+use bevy_ratatui::{
+    BevyBackend, ProxyBackend, RatatuiPlugin, TermEvent, TermEvents, TermKey, TerminalComponent,
+    ThreadedApp,
+};
+
+/// An ordinary ratatui app loop, written the way it would be for crossterm, running on its own
+/// thread inside Bevy.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RatatuiPlugin)
+        .add_systems(Startup, setup)
+        .add_systems(Update, exit_with_app)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let mut my_terminal = Terminal::new(BevyBackend::default()).unwrap();
+    let _ = my_terminal.clear();
+
+    commands.spawn((
+        TerminalComponent {
+            ratatui_terminal: my_terminal,
+        },
+        ThreadedApp::new(run_app),
+    ));
+}
+
+fn run_app(mut terminal: Terminal<ProxyBackend>, mut events: TermEvents) -> io::Result<()> {
+    let tick_rate = Duration::from_millis(250);
+    let mut last_tick = Instant::now();
+    let mut ticks = 0u64;
+    let mut counter = 0i64;
+    loop {
+        terminal.draw(|f| {
+            let text = vec![
+                Line::from(format!("Counter: {}", counter)),
+                Line::from(format!("Ticks: {}", ticks)),
+                Line::from("Up/Down to count, q to quit"),
+            ];
+            let block = Block::default().borders(Borders::ALL).title("Threaded");
+            f.render_widget(Paragraph::new(text).block(block), f.size());
+        })?;
+
+        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+        if events.poll(timeout)? {
+            if let TermEvent::Key(key) = events.read()? {
+                match key.key {
+                    TermKey::Char('q') => return Ok(()),
+                    TermKey::Up => counter += 1,
+                    TermKey::Down => counter -= 1,
+                    _ => {}
+                }
+            }
+        }
+        if last_tick.elapsed() >= tick_rate {
+            ticks += 1;
+            last_tick = Instant::now();
+        }
+    }
+}
+
+fn exit_with_app(apps: Query<&ThreadedApp>, mut exit: EventWriter<AppExit>) {
+    if apps.iter().any(|app| app.result.is_some()) {
+        exit.send(AppExit);
+    }
+}
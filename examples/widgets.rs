@@ -0,0 +1,99 @@
+// [Ratatui] Widget components example
+
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    utils::Duration,
+    winit::{UpdateMode, WinitSettings},
+};
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Style, Stylize},
+    widgets::*,
+    Terminal,
+};
+
+use bevy_ratatui::{
+    BevyBackend, RatWidget, RatatuiPlugin, ReactiveRendering, StatefulRatWidget, TerminalComponent,
+};
+
+/// Panels are widget components on children of the terminal entity, the plugin draws them. Each
+/// panel is updated by its own system and nobody calls `draw`. Frames only run on input, when
+/// the terminal changed, or once a second for the clock.
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RatatuiPlugin)
+        .insert_resource(WinitSettings {
+            focused_mode: UpdateMode::Reactive {
+                wait: Duration::from_secs(1),
+            },
+            ..WinitSettings::desktop_app()
+        })
+        .insert_resource(ReactiveRendering::default())
+        .add_systems(Startup, setup)
+        .add_systems(Update, (move_selection, update_clock, keyboard_input))
+        .run();
+}
+
+#[derive(Component)]
+struct Clock;
+
+fn setup(mut commands: Commands) {
+    commands.spawn(Camera2dBundle::default());
+
+    let mut my_terminal = Terminal::new(BevyBackend::default()).unwrap();
+    let _ = my_terminal.clear();
+
+    let items = ["Sword", "Shield", "Potion", "Map", "Lantern", "Rope"];
+    commands
+        .spawn(TerminalComponent {
+            ratatui_terminal: my_terminal,
+        })
+        .with_children(|parent| {
+            parent.spawn(RatWidget::new(
+                Rect::new(0, 0, 40, 12),
+                Block::default().borders(Borders::ALL).title("Inventory"),
+            ));
+            parent.spawn(StatefulRatWidget::new(
+                Rect::new(1, 1, 38, 10),
+                List::new(items)
+                    .highlight_style(Style::new().reversed())
+                    .highlight_symbol("> "),
+                ListState::default().with_selected(Some(0)),
+            ));
+            // drawn over the list
+            parent.spawn((
+                RatWidget::new(Rect::new(24, 0, 15, 1), Paragraph::new("")).z(1),
+                Clock,
+            ));
+        });
+}
+
+fn move_selection(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut lists: Query<&mut StatefulRatWidget<ListState>>,
+) {
+    for mut list in lists.iter_mut() {
+        let selected = list.state.selected().unwrap_or(0);
+        if keys.just_pressed(KeyCode::ArrowDown) {
+            list.state.select(Some((selected + 1).min(5)));
+        }
+        if keys.just_pressed(KeyCode::ArrowUp) {
+            list.state.select(Some(selected.saturating_sub(1)));
+        }
+    }
+}
+
+fn update_clock(time: Res<Time>, mut clocks: Query<&mut RatWidget, With<Clock>>) {
+    for mut clock in clocks.iter_mut() {
+        let text = format!(" {:.0}s ", time.elapsed_seconds().floor());
+        clock.widget = Box::new(Paragraph::new(text).alignment(Alignment::Right));
+    }
+}
+
+fn keyboard_input(keys: Res<ButtonInput<KeyCode>>, mut exit: EventWriter<AppExit>) {
+    if keys.just_pressed(KeyCode::KeyQ) {
+        exit.send(AppExit);
+    }
+}
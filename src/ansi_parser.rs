@@ -0,0 +1,661 @@
+//! A small VT/ANSI parser that plays terminal output into a [`BevyBackend`]. It understands
+//! printable text with wrapping, carriage return, line feed, backspace and tab, CSI cursor moves,
+//! erase in display and line, scrolling regions, line and character insert/delete, the alternate
+//! screen, SGR colours and modifiers, cursor visibility and save/restore.
+//! Anything else (OSC titles, modes, DCS) is skipped. A parser follows the size of the backend, or
+//! lays the output out at a fixed size and clips it with [`AnsiParser::with_size`].
+
+use ratatui::{
+    backend::Backend,
+    buffer::Cell,
+    style::{Color as RatColor, Modifier},
+};
+use unicode_width::UnicodeWidthChar;
+
+use crate::BevyBackend;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum ParseState {
+    #[default]
+    Ground,
+    Escape,
+    Csi,
+    /// The character set after `ESC (`, `ESC )` and the like, ignored
+    Charset,
+    /// OSC, DCS and friends, skipped up to BEL or ST
+    String,
+    StringEscape,
+}
+
+/// The screen being written to while a chunk is parsed, drawn into the backend in one batch at
+/// the end so scrolling doesn't redraw the terminal for every line.
+struct Screen {
+    width: u16,
+    height: u16,
+    content: Vec<Cell>,
+    dirty: Vec<bool>,
+    cursor_visible: Option<bool>,
+}
+
+impl Screen {
+    fn set(&mut self, x: u16, y: u16, cell: Cell) {
+        let index = y as usize * self.width as usize + x as usize;
+        self.content[index] = cell;
+        self.dirty[index] = true;
+    }
+
+    fn get_mut(&mut self, x: u16, y: u16) -> &mut Cell {
+        let index = y as usize * self.width as usize + x as usize;
+        self.dirty[index] = true;
+        &mut self.content[index]
+    }
+
+    /// Scrolls rows top..=bottom by n lines, up when n is positive, filling with blanks.
+    fn scroll(&mut self, top: u16, bottom: u16, n: i32, blank: Cell) {
+        let width = self.width as usize;
+        let rows = &mut self.content[top as usize * width..(bottom as usize + 1) * width];
+        let shift = (n.unsigned_abs() as usize * width).min(rows.len());
+        let len = rows.len();
+        if n > 0 {
+            rows.rotate_left(shift);
+            rows[len - shift..].fill(blank);
+        } else {
+            rows.rotate_right(shift);
+            rows[..shift].fill(blank);
+        }
+        self.dirty[top as usize * width..(bottom as usize + 1) * width].fill(true);
+    }
+
+    /// Shifts the rest of row y right of x by n cells, right when n is positive.
+    fn shift_row(&mut self, x: u16, y: u16, n: i32, blank: Cell) {
+        let width = self.width as usize;
+        let start = y as usize * width;
+        let row = &mut self.content[start + x as usize..start + width];
+        let shift = (n.unsigned_abs() as usize).min(row.len());
+        let len = row.len();
+        if n > 0 {
+            row.rotate_right(shift);
+            row[..shift].fill(blank);
+        } else {
+            row.rotate_left(shift);
+            row[len - shift..].fill(blank);
+        }
+        self.dirty[start + x as usize..start + width].fill(true);
+    }
+}
+
+/// Parser state carried between chunks of output, so escape sequences may be split anywhere.
+#[derive(Debug, Clone, Default)]
+pub struct AnsiParser {
+    state: ParseState,
+    params: String,
+    pub cursor: (u16, u16),
+    saved_cursor: (u16, u16),
+    pub fg: RatColor,
+    pub bg: RatColor,
+    pub modifier: Modifier,
+    /// Set after writing the last column, the next printable wraps first
+    pending_wrap: bool,
+    /// Where the last printable went, combining marks are added to it
+    last_printed: Option<(u16, u16)>,
+    /// Screen size the last chunk was laid out at
+    laid_out: (u16, u16),
+    /// Top and bottom rows of the scrolling region, None for the whole screen
+    scroll_region: Option<(u16, u16)>,
+    /// Main screen and cursor, kept while the alternate screen is shown
+    main_screen: Option<(Vec<Cell>, (u16, u16))>,
+    /// Screen size the output is laid out for, None to follow the backend
+    size: Option<(u16, u16)>,
+    /// The whole screen of a sized parser, only the part that fits the backend is drawn
+    screen: Vec<Cell>,
+}
+
+impl AnsiParser {
+    pub fn new() -> Self {
+        AnsiParser::default()
+    }
+
+    /// A parser for output written for a screen of a fixed size, like a recording. The output
+    /// wraps and scrolls at that size and whatever doesn't fit the backend is clipped.
+    pub fn with_size(width: u16, height: u16) -> Self {
+        AnsiParser {
+            size: Some((width, height)),
+            ..AnsiParser::default()
+        }
+    }
+
+    /// Parses a chunk of output and draws the cells it changes into the backend.
+    pub fn feed(&mut self, data: &str, backend: &mut BevyBackend) {
+        let backend_size = (backend.buffer.area.width, backend.buffer.area.height);
+        let (width, height) = self.size.unwrap_or(backend_size);
+        if width == 0 || height == 0 || backend_size.0 == 0 || backend_size.1 == 0 {
+            return;
+        }
+        let content = match self.size {
+            Some(_) => {
+                let len = width as usize * height as usize;
+                if self.screen.len() != len {
+                    self.screen = vec![Cell::default(); len];
+                }
+                std::mem::take(&mut self.screen)
+            }
+            None => backend.buffer.content.clone(),
+        };
+        let mut screen = Screen {
+            width,
+            height,
+            dirty: vec![false; content.len()],
+            content,
+            cursor_visible: None,
+        };
+        self.cursor = (self.cursor.0.min(width - 1), self.cursor.1.min(height - 1));
+        // the last printed cell may be off a screen that shrank, marks after a resize stand alone
+        if self.laid_out != (width, height) {
+            self.last_printed = None;
+            self.laid_out = (width, height);
+        }
+
+        for c in data.chars() {
+            match self.state {
+                ParseState::Ground => self.ground(c, &mut screen),
+                ParseState::Escape => match c {
+                    '[' => {
+                        self.params.clear();
+                        self.state = ParseState::Csi;
+                    }
+                    ']' | 'P' | 'X' | '^' | '_' => self.state = ParseState::String,
+                    'D' => {
+                        self.line_feed(&mut screen);
+                        self.state = ParseState::Ground;
+                    }
+                    'E' => {
+                        self.cursor.0 = 0;
+                        self.line_feed(&mut screen);
+                        self.state = ParseState::Ground;
+                    }
+                    'M' => {
+                        self.reverse_index(&mut screen);
+                        self.state = ParseState::Ground;
+                    }
+                    '7' => {
+                        self.saved_cursor = self.cursor;
+                        self.state = ParseState::Ground;
+                    }
+                    '8' => {
+                        self.restore_cursor(&screen);
+                        self.state = ParseState::Ground;
+                    }
+                    // charset designations take one more character
+                    '(' | ')' | '*' | '+' => self.state = ParseState::Charset,
+                    _ => self.state = ParseState::Ground,
+                },
+                ParseState::Csi => {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        self.csi(c, &mut screen);
+                        self.state = ParseState::Ground;
+                    } else {
+                        self.params.push(c);
+                    }
+                }
+                ParseState::Charset => self.state = ParseState::Ground,
+                ParseState::String => match c {
+                    '\x07' => self.state = ParseState::Ground,
+                    '\x1b' => self.state = ParseState::StringEscape,
+                    _ => {}
+                },
+                ParseState::StringEscape => {
+                    self.state = if c == '\\' {
+                        ParseState::Ground
+                    } else {
+                        ParseState::String
+                    };
+                }
+            }
+        }
+
+        let changed = screen
+            .content
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| screen.dirty[*i])
+            .map(|(i, cell)| {
+                (
+                    (i % width as usize) as u16,
+                    (i / width as usize) as u16,
+                    cell,
+                )
+            })
+            .filter(|(x, y, _)| *x < backend_size.0 && *y < backend_size.1);
+        let _ = backend.draw(changed);
+        let _ = backend.set_cursor(
+            self.cursor.0.min(backend_size.0 - 1),
+            self.cursor.1.min(backend_size.1 - 1),
+        );
+        let _ = match screen.cursor_visible {
+            Some(true) => backend.show_cursor(),
+            Some(false) => backend.hide_cursor(),
+            None => Ok(()),
+        };
+        if self.size.is_some() {
+            self.screen = screen.content;
+        }
+    }
+
+    fn ground(&mut self, c: char, screen: &mut Screen) {
+        match c {
+            '\x1b' => self.state = ParseState::Escape,
+            '\r' => {
+                self.cursor.0 = 0;
+                self.pending_wrap = false;
+            }
+            '\n' | '\x0b' | '\x0c' => {
+                self.line_feed(screen);
+                self.pending_wrap = false;
+            }
+            '\x08' => {
+                self.cursor.0 = self.cursor.0.saturating_sub(1);
+                self.pending_wrap = false;
+            }
+            '\t' => {
+                self.cursor.0 = ((self.cursor.0 / 8 + 1) * 8).min(screen.width - 1);
+                self.pending_wrap = false;
+            }
+            c if c.is_control() => {}
+            c => self.print(c, screen),
+        }
+    }
+
+    fn print(&mut self, c: char, screen: &mut Screen) {
+        let width = c.width().unwrap_or(0) as u16;
+        if width == 0 {
+            // combining marks join the previous cell
+            if let Some((x, y)) = self.last_printed {
+                let cell = screen.get_mut(x, y);
+                let symbol = format!("{}{}", cell.symbol(), c);
+                cell.set_symbol(&symbol);
+            }
+            return;
+        }
+
+        if self.pending_wrap || self.cursor.0 + width > screen.width {
+            self.cursor.0 = 0;
+            self.line_feed(screen);
+            self.pending_wrap = false;
+        }
+
+        let mut cell = self.blank();
+        cell.set_char(c);
+        screen.set(self.cursor.0, self.cursor.1, cell);
+        self.last_printed = Some(self.cursor);
+        // the cells a wide character covers are blanked, so nothing drawn there before shows
+        for x in self.cursor.0 + 1..(self.cursor.0 + width).min(screen.width) {
+            screen.set(x, self.cursor.1, self.blank());
+        }
+
+        if self.cursor.0 + width >= screen.width {
+            self.cursor.0 = screen.width - 1;
+            self.pending_wrap = true;
+        } else {
+            self.cursor.0 += width;
+        }
+    }
+
+    /// Top and bottom rows that scroll.
+    fn region(&self, screen: &Screen) -> (u16, u16) {
+        self.scroll_region
+            .filter(|(top, bottom)| top < bottom && *bottom < screen.height)
+            .unwrap_or((0, screen.height - 1))
+    }
+
+    /// Moves down a line, scrolling the region up when the cursor is on its bottom line.
+    fn line_feed(&mut self, screen: &mut Screen) {
+        let (top, bottom) = self.region(screen);
+        self.pending_wrap = false;
+        if self.cursor.1 == bottom {
+            screen.scroll(top, bottom, 1, self.erased());
+            self.last_printed = self
+                .last_printed
+                .and_then(|(x, y)| Some((x, y.checked_sub(1)?)));
+        } else if self.cursor.1 + 1 < screen.height {
+            self.cursor.1 += 1;
+        }
+    }
+
+    /// Moves up a line, scrolling the region down when the cursor is on its top line.
+    fn reverse_index(&mut self, screen: &mut Screen) {
+        let (top, bottom) = self.region(screen);
+        self.pending_wrap = false;
+        if self.cursor.1 == top {
+            screen.scroll(top, bottom, -1, self.erased());
+        } else {
+            self.cursor.1 = self.cursor.1.saturating_sub(1);
+        }
+    }
+
+    /// Switches to or from the alternate screen that full screen programs draw on.
+    fn alternate_screen(&mut self, enable: bool, screen: &mut Screen) {
+        if enable && self.main_screen.is_none() {
+            self.main_screen = Some((screen.content.clone(), self.cursor));
+            let (max_x, max_y) = (screen.width - 1, screen.height - 1);
+            self.erase((0, 0), (max_x, max_y), screen);
+        } else if !enable {
+            if let Some((content, cursor)) = self.main_screen.take() {
+                if content.len() == screen.content.len() {
+                    screen.content = content;
+                    screen.dirty.fill(true);
+                }
+                self.cursor = (
+                    cursor.0.min(screen.width - 1),
+                    cursor.1.min(screen.height - 1),
+                );
+            }
+        }
+    }
+
+    /// Goes back to the saved cursor, which may be off a screen that shrank since.
+    fn restore_cursor(&mut self, screen: &Screen) {
+        self.cursor = (
+            self.saved_cursor.0.min(screen.width - 1),
+            self.saved_cursor.1.min(screen.height - 1),
+        );
+        self.pending_wrap = false;
+    }
+
+    /// A cell with the current colours, before the symbol is set.
+    fn blank(&self) -> Cell {
+        let mut cell = Cell::default();
+        cell.set_fg(self.fg).set_bg(self.bg).modifier = self.modifier;
+        cell
+    }
+
+    /// What erasing leaves behind, the current background without any modifiers.
+    fn erased(&self) -> Cell {
+        let mut cell = Cell::default();
+        cell.set_bg(self.bg);
+        cell
+    }
+
+    /// Erases from one position to another inclusive, in reading order.
+    fn erase(&self, from: (u16, u16), to: (u16, u16), screen: &mut Screen) {
+        let width = screen.width as usize;
+        let start = from.1 as usize * width + from.0 as usize;
+        let end = to.1 as usize * width + to.0 as usize;
+        for i in start..=end {
+            screen.set((i % width) as u16, (i / width) as u16, self.erased());
+        }
+    }
+
+    fn csi(&mut self, action: char, screen: &mut Screen) {
+        let private = self.params.starts_with('?');
+        let params: Vec<u16> = self
+            .params
+            .trim_start_matches('?')
+            .split(';')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect();
+        let arg = |i: usize, default: u16| match params.get(i) {
+            Some(0) | None => default,
+            Some(n) => *n,
+        };
+        let (max_x, max_y) = (screen.width - 1, screen.height - 1);
+        self.pending_wrap = false;
+
+        match action {
+            'A' => self.cursor.1 = self.cursor.1.saturating_sub(arg(0, 1)),
+            'B' | 'e' => self.cursor.1 = self.cursor.1.saturating_add(arg(0, 1)).min(max_y),
+            'C' | 'a' => self.cursor.0 = self.cursor.0.saturating_add(arg(0, 1)).min(max_x),
+            'D' => self.cursor.0 = self.cursor.0.saturating_sub(arg(0, 1)),
+            'E' => self.cursor = (0, self.cursor.1.saturating_add(arg(0, 1)).min(max_y)),
+            'F' => self.cursor = (0, self.cursor.1.saturating_sub(arg(0, 1))),
+            'G' | '`' => self.cursor.0 = (arg(0, 1) - 1).min(max_x),
+            'd' => self.cursor.1 = (arg(0, 1) - 1).min(max_y),
+            'H' | 'f' => self.cursor = ((arg(1, 1) - 1).min(max_x), (arg(0, 1) - 1).min(max_y)),
+            'J' => match params[0] {
+                0 => self.erase(self.cursor, (max_x, max_y), screen),
+                1 => self.erase((0, 0), self.cursor, screen),
+                _ => self.erase((0, 0), (max_x, max_y), screen),
+            },
+            'K' => {
+                let y = self.cursor.1;
+                match params[0] {
+                    0 => self.erase(self.cursor, (max_x, y), screen),
+                    1 => self.erase((0, y), self.cursor, screen),
+                    _ => self.erase((0, y), (max_x, y), screen),
+                }
+            }
+            'X' => {
+                let end = self.cursor.0.saturating_add(arg(0, 1) - 1).min(max_x);
+                self.erase(self.cursor, (end, self.cursor.1), screen);
+            }
+            'm' => self.sgr(&params),
+            'r' if !private => {
+                let (top, bottom) = (arg(0, 1) - 1, arg(1, screen.height) - 1);
+                self.scroll_region = (top < bottom && bottom <= max_y).then_some((top, bottom));
+                self.cursor = (0, 0);
+            }
+            'S' | 'T' => {
+                let (top, bottom) = self.region(screen);
+                let n = arg(0, 1) as i32;
+                let n = if action == 'S' { n } else { -n };
+                screen.scroll(top, bottom, n, self.erased());
+            }
+            'L' | 'M' => {
+                let (top, bottom) = self.region(screen);
+                if (top..=bottom).contains(&self.cursor.1) {
+                    let n = arg(0, 1) as i32;
+                    let n = if action == 'M' { n } else { -n };
+                    screen.scroll(self.cursor.1, bottom, n, self.erased());
+                    self.cursor.0 = 0;
+                }
+            }
+            '@' | 'P' => {
+                let n = arg(0, 1) as i32;
+                let n = if action == '@' { n } else { -n };
+                screen.shift_row(self.cursor.0, self.cursor.1, n, self.erased());
+            }
+            's' => self.saved_cursor = self.cursor,
+            'u' => self.restore_cursor(screen),
+            'h' | 'l' if private => {
+                for mode in &params {
+                    match mode {
+                        25 => screen.cursor_visible = Some(action == 'h'),
+                        47 | 1047 | 1049 => self.alternate_screen(action == 'h', screen),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn sgr(&mut self, params: &[u16]) {
+        let mut i = 0;
+        while i < params.len() {
+            match params[i] {
+                0 => {
+                    self.fg = RatColor::Reset;
+                    self.bg = RatColor::Reset;
+                    self.modifier = Modifier::empty();
+                }
+                1 => self.modifier.insert(Modifier::BOLD),
+                2 => self.modifier.insert(Modifier::DIM),
+                3 => self.modifier.insert(Modifier::ITALIC),
+                4 => self.modifier.insert(Modifier::UNDERLINED),
+                5 => self.modifier.insert(Modifier::SLOW_BLINK),
+                6 => self.modifier.insert(Modifier::RAPID_BLINK),
+                7 => self.modifier.insert(Modifier::REVERSED),
+                8 => self.modifier.insert(Modifier::HIDDEN),
+                9 => self.modifier.insert(Modifier::CROSSED_OUT),
+                22 => self.modifier.remove(Modifier::BOLD | Modifier::DIM),
+                23 => self.modifier.remove(Modifier::ITALIC),
+                24 => self.modifier.remove(Modifier::UNDERLINED),
+                25 => self
+                    .modifier
+                    .remove(Modifier::SLOW_BLINK | Modifier::RAPID_BLINK),
+                27 => self.modifier.remove(Modifier::REVERSED),
+                28 => self.modifier.remove(Modifier::HIDDEN),
+                29 => self.modifier.remove(Modifier::CROSSED_OUT),
+                n @ 30..=37 => self.fg = named_color(n - 30),
+                n @ 40..=47 => self.bg = named_color(n - 40),
+                n @ 90..=97 => self.fg = named_color(n - 90 + 8),
+                n @ 100..=107 => self.bg = named_color(n - 100 + 8),
+                39 => self.fg = RatColor::Reset,
+                49 => self.bg = RatColor::Reset,
+                n @ (38 | 48) => {
+                    let color = match params.get(i + 1) {
+                        Some(5) => {
+                            i += 2;
+                            params.get(i).map(|c| RatColor::Indexed(*c as u8))
+                        }
+                        Some(2) => {
+                            i += 4;
+                            match params.get(i - 2..=i) {
+                                Some([r, g, b]) => {
+                                    Some(RatColor::Rgb(*r as u8, *g as u8, *b as u8))
+                                }
+                                _ => None,
+                            }
+                        }
+                        _ => None,
+                    };
+                    if let Some(color) = color {
+                        if n == 38 {
+                            self.fg = color;
+                        } else {
+                            self.bg = color;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// The 16 named colours in ANSI order, normal then bright.
+fn named_color(index: u16) -> RatColor {
+    match index {
+        0 => RatColor::Black,
+        1 => RatColor::Red,
+        2 => RatColor::Green,
+        3 => RatColor::Yellow,
+        4 => RatColor::Blue,
+        5 => RatColor::Magenta,
+        6 => RatColor::Cyan,
+        7 => RatColor::Gray,
+        8 => RatColor::DarkGray,
+        9 => RatColor::LightRed,
+        10 => RatColor::LightGreen,
+        11 => RatColor::LightYellow,
+        12 => RatColor::LightBlue,
+        13 => RatColor::LightMagenta,
+        14 => RatColor::LightCyan,
+        _ => RatColor::White,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(width: u16, height: u16, data: &str) -> (AnsiParser, BevyBackend) {
+        let mut backend = BevyBackend::default();
+        backend.resize(width, height);
+        let mut parser = AnsiParser::new();
+        parser.feed(data, &mut backend);
+        (parser, backend)
+    }
+
+    fn symbols(backend: &BevyBackend, y: u16) -> Vec<&str> {
+        (0..backend.buffer.area.width)
+            .map(|x| backend.buffer.get(x, y).symbol())
+            .collect()
+    }
+
+    #[test]
+    fn combining_mark_after_a_shrink_stands_alone() {
+        let (mut parser, mut backend) = play(4, 2, "abcd\r\nxy");
+        backend.resize(2, 1);
+        // the cursor is clamped to the last column, the mark has nothing to join
+        parser.feed("\u{301}e", &mut backend);
+        assert_eq!(parser.last_printed, Some((1, 0)));
+        assert_eq!(backend.buffer.get(1, 0).symbol(), "e");
+    }
+
+    #[test]
+    fn prints_and_wraps() {
+        let (parser, backend) = play(4, 3, "abcdef\r\nx");
+        assert_eq!(backend.snapshot_text(), "abcd\nef\nx\n");
+        assert_eq!(parser.cursor, (1, 2));
+    }
+
+    #[test]
+    fn wide_char_blanks_the_cell_it_covers() {
+        let (parser, backend) = play(6, 1, "abcdef\r世x");
+        assert_eq!(symbols(&backend, 0), ["世", " ", "x", "d", "e", "f"]);
+        assert_eq!(parser.cursor, (3, 0));
+    }
+
+    #[test]
+    fn wide_char_wraps_instead_of_splitting() {
+        let (_, backend) = play(3, 2, "ab世");
+        assert_eq!(symbols(&backend, 0), ["a", "b", " "]);
+        assert_eq!(symbols(&backend, 1), ["世", " ", " "]);
+    }
+
+    #[test]
+    fn charset_designation_is_consumed() {
+        // ESC ( D would otherwise be read as ESC D, a line feed
+        let (parser, backend) = play(4, 2, "\x1b(Da\x1b)0b");
+        assert_eq!(backend.snapshot_text(), "ab\n\n");
+        assert_eq!(parser.cursor, (2, 0));
+    }
+
+    #[test]
+    fn tab_clears_pending_wrap() {
+        let (parser, backend) = play(10, 2, "0123456789\tx");
+        assert_eq!(symbols(&backend, 0)[9], "x");
+        assert_eq!(backend.snapshot_text(), "012345678x\n\n");
+        assert_eq!(parser.cursor, (9, 0));
+    }
+
+    #[test]
+    fn sequences_split_across_chunks() {
+        let mut backend = BevyBackend::default();
+        backend.resize(4, 2);
+        let mut parser = AnsiParser::new();
+        for chunk in ["\x1b", "[3", "1mr", "\x1b[2;", "3Hz"] {
+            parser.feed(chunk, &mut backend);
+        }
+        assert_eq!(backend.buffer.get(0, 0).symbol(), "r");
+        assert_eq!(backend.buffer.get(0, 0).fg, RatColor::Red);
+        assert_eq!(backend.buffer.get(2, 1).symbol(), "z");
+    }
+
+    #[test]
+    fn sgr_colours_and_modifiers() {
+        let (_, backend) = play(4, 1, "\x1b[1;38;5;42;48;2;1;2;3ma\x1b[22;39mb\x1b[0mc");
+        let a = backend.buffer.get(0, 0);
+        assert_eq!(a.fg, RatColor::Indexed(42));
+        assert_eq!(a.bg, RatColor::Rgb(1, 2, 3));
+        assert!(a.modifier.contains(Modifier::BOLD));
+        let b = backend.buffer.get(1, 0);
+        assert_eq!(b.fg, RatColor::Reset);
+        assert_eq!(b.bg, RatColor::Rgb(1, 2, 3));
+        assert!(b.modifier.is_empty());
+        assert_eq!(backend.buffer.get(2, 0).bg, RatColor::Reset);
+    }
+
+    #[test]
+    fn erase_and_scroll() {
+        let (_, backend) = play(3, 3, "abc\r\ndef\r\nghi\r\njk\x1b[1;2H\x1b[K");
+        assert_eq!(backend.snapshot_text(), "d\nghi\njk\n");
+    }
+
+    #[test]
+    fn alternate_screen_restores_the_main_screen() {
+        let (_, backend) = play(3, 1, "ab\x1b[?1049hxyz\x1b[?1049l");
+        assert_eq!(backend.snapshot_text(), "ab\n");
+    }
+}
@@ -5,27 +5,77 @@ use std::io;
 
 use bevy::{prelude::*, utils::HashMap};
 
+use crate::{
+    bitmap_font::BitmapFont,
+    export::{ansi_batch, export_ansi, export_html, export_text, screen_cells},
+    mirror::TerminalMirror,
+    recording::CastRecorder,
+    tileset::Tileset,
+};
+
 use ratatui::{
     backend::{Backend, ClearType, WindowSize},
     buffer::{Buffer, Cell},
-    layout::{Rect, Size},
+    layout::{Position, Rect, Size},
 };
 
+/// A Bevy image drawn over a region of the terminal, scaled to fill it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminalImage {
+    pub area: Rect,
+    pub image: Handle<Image>,
+    /// UI node showing the image, PLACEHOLDER until the plugin spawns it
+    pub entity: Entity,
+}
+
+/// How a terminal fits its grid into the window, or into its parent node when embedded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TerminalSizing {
+    /// Resize the grid to fill the area, within the min and max grid limits
+    #[default]
+    FitWindow,
+    /// Keep the grid size and scale the font to fill the area, letterboxing what is left over
+    ScaleFont,
+    /// Like ScaleFont but only at whole multiples of the font size, for pixel fonts
+    IntegerScale,
+    /// Never touch the grid or the font, sizing is left to the user
+    Manual,
+}
+
+/// How the cells of a terminal are turned into Bevy UI entities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderMode {
+    /// One entity per cell, supports everything: bitmap fonts, tilesets, procedural box drawing
+    /// and blinking
+    #[default]
+    Cells,
+    /// One text entity per row with a section per run of the same style, and a background quad
+    /// per run of the same background. Far fewer entities on large grids, but only TTF fonts are
+    /// drawn and cells don't blink
+    Rows,
+}
+
 ///RATATUI SPECIFIC STUFF STARTS HERE
 ///
 ///
 /// Bevy Backend is created either with default() which uses the built in Bevy font which is VERY
 /// bad and not recommended, or with new() which takes the height,width font size, and path strings
 /// to fonts to be used for the normal, bold, italic, and bold italic text variants.
+/// Paths to BDF, PSF or CP437 tilesheet fonts are loaded as bitmap fonts, see [`BitmapFont`].
 
 #[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BevyBackend {
     pub height: u16,
     pub width: u16,
     pub term_font_size: u16,
-    /// HashMap with position tuple index and UI Entity id (cell) in that position
-    pub entity_map: HashMap<(u16, u16), Entity>,
+    /// UI entity of every cell row by row, empty until the plugin spawns the cells. Look cells
+    /// up with [`BevyBackend::cell_entity`]
+    pub cell_entities: Vec<Entity>,
+    /// Columns of the grid the cell entities were spawned for
+    pub cell_columns: u16,
+    pub render_mode: RenderMode,
+    /// Background and text entity of every row in [`RenderMode::Rows`]
+    pub row_entities: Vec<(Entity, Entity)>,
     pub buffer: Buffer,
 
     pub vcupdate: Vec<(u16, u16, Cell)>,
@@ -42,7 +92,41 @@ pub struct BevyBackend {
     pub italic_handle: Handle<Font>,
     pub bold_handle: Handle<Font>,
     pub italicbold_handle: Handle<Font>,
-    pub manual_window_sizing: bool,
+    /// Bitmap fonts, loaded instead of the TTF handles when a font path is a bitmap font
+    pub normal_bitmap: Option<Handle<BitmapFont>>,
+    pub italic_bitmap: Option<Handle<BitmapFont>>,
+    pub bold_bitmap: Option<Handle<BitmapFont>>,
+    pub italicbold_bitmap: Option<Handle<BitmapFont>>,
+    /// Draw box drawing, block and braille characters procedurally so they join across cells,
+    /// off by default
+    pub geometric_glyphs: bool,
+    /// Procedural glyphs drawn at the current cell size
+    pub geometric_font: Option<Handle<BitmapFont>>,
+    /// Sprite tiles drawn in place of matching cells
+    pub tileset: Option<Tileset>,
+    /// Images drawn over regions of the grid
+    pub images: Vec<TerminalImage>,
+    /// Nodes of images that were overwritten, waiting to be despawned
+    pub stale_images: Vec<Entity>,
+    pub sizing: TerminalSizing,
+    /// Smallest grid (columns, rows) FitWindow will shrink to
+    pub min_grid: (u16, u16),
+    /// Largest grid (columns, rows) FitWindow will grow to
+    pub max_grid: (u16, u16),
+    /// Multiplier applied to term_font_size by the scaling policies
+    pub font_scale: f32,
+    /// Padding in pixels that centres the grid in the area it was fitted to
+    pub grid_offset: Vec2,
+    /// Logical size of one cell, snapped to whole physical pixels
+    pub cell_size: Vec2,
+    /// Scale factor of the window the terminal is drawn in
+    pub scale_factor: f32,
+    /// Lay the terminal out inside the UI node it is a child of instead of owning the window
+    pub embedded: bool,
+    /// Asciinema recording of every draw, while one is running
+    pub recorder: Option<CastRecorder>,
+    /// Real terminal every draw is also written to
+    pub mirror: Option<TerminalMirror>,
 }
 
 impl Default for BevyBackend {
@@ -51,7 +135,10 @@ impl Default for BevyBackend {
             height: 25,
             width: 40,
             term_font_size: 30,
-            entity_map: HashMap::new(),
+            cell_entities: Vec::new(),
+            cell_columns: 0,
+            render_mode: RenderMode::default(),
+            row_entities: Vec::new(),
             buffer: Buffer::empty(Rect::new(0, 0, 40, 25)),
 
             vcupdate: Vec::default(),
@@ -68,7 +155,25 @@ impl Default for BevyBackend {
             italic_handle: Handle::weak_from_u128(101),
             bold_handle: Handle::weak_from_u128(101),
             italicbold_handle: Handle::weak_from_u128(101),
-            manual_window_sizing: false,
+            normal_bitmap: None,
+            italic_bitmap: None,
+            bold_bitmap: None,
+            italicbold_bitmap: None,
+            geometric_glyphs: false,
+            geometric_font: None,
+            tileset: None,
+            images: Vec::new(),
+            stale_images: Vec::new(),
+            sizing: TerminalSizing::default(),
+            min_grid: (1, 1),
+            max_grid: (u16::MAX, u16::MAX),
+            font_scale: 1.0,
+            grid_offset: Vec2::ZERO,
+            cell_size: Vec2::ZERO,
+            scale_factor: 1.0,
+            embedded: false,
+            recorder: None,
+            mirror: None,
         }
     }
 }
@@ -85,10 +190,13 @@ impl BevyBackend {
         italicbold_font_path: &str,
     ) -> BevyBackend {
         BevyBackend {
-            height: height,
-            width: width,
+            height,
+            width,
             term_font_size: font_size,
-            entity_map: HashMap::new(),
+            cell_entities: Vec::new(),
+            cell_columns: 0,
+            render_mode: RenderMode::default(),
+            row_entities: Vec::new(),
             buffer: Buffer::empty(Rect::new(0, 0, width, height)),
 
             vcupdate: Vec::default(),
@@ -105,7 +213,25 @@ impl BevyBackend {
             italic_handle: Handle::weak_from_u128(101),
             bold_handle: Handle::weak_from_u128(101),
             italicbold_handle: Handle::weak_from_u128(101),
-            manual_window_sizing: false,
+            normal_bitmap: None,
+            italic_bitmap: None,
+            bold_bitmap: None,
+            italicbold_bitmap: None,
+            geometric_glyphs: false,
+            geometric_font: None,
+            tileset: None,
+            images: Vec::new(),
+            stale_images: Vec::new(),
+            sizing: TerminalSizing::default(),
+            min_grid: (1, 1),
+            max_grid: (u16::MAX, u16::MAX),
+            font_scale: 1.0,
+            grid_offset: Vec2::ZERO,
+            cell_size: Vec2::ZERO,
+            scale_factor: 1.0,
+            embedded: false,
+            recorder: None,
+            mirror: None,
         }
     }
 
@@ -114,11 +240,261 @@ impl BevyBackend {
         self.buffer.resize(Rect::new(0, 0, width, height));
         self.width = width;
         self.height = height;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_resize(width, height);
+        }
     }
 
-    /// Resizes the BevyBackend to the specified width and height.
+    /// UI entity of the cell at x, y. None before the cells are spawned, or for cells outside the
+    /// grid they were spawned for while they are being rebuilt after a resize.
+    pub fn cell_entity(&self, x: u16, y: u16) -> Option<Entity> {
+        if x >= self.cell_columns {
+            return None;
+        }
+        let index = y as usize * self.cell_columns as usize + x as usize;
+        self.cell_entities.get(index).copied()
+    }
+
+    /// The cell entities by their x, y, built from `cell_entities` on every call. This used to be
+    /// the `entity_map` field, look single cells up with [`BevyBackend::cell_entity`] instead or
+    /// walk `cell_entities`, which is row by row `cell_columns` wide.
+    #[deprecated(note = "use `cell_entity(x, y)` or the `cell_entities` field")]
+    pub fn entity_map(&self) -> HashMap<(u16, u16), Entity> {
+        let columns = self.cell_columns.max(1) as usize;
+        self.cell_entities
+            .iter()
+            .enumerate()
+            .map(|(i, entity)| (((i % columns) as u16, (i / columns) as u16), *entity))
+            .collect()
+    }
+
+    /// Turns off automatic sizing, the grid stays whatever size you give it.
     pub fn manual_window_sizing(&mut self, value: bool) {
-        self.manual_window_sizing = value;
+        self.sizing = if value {
+            TerminalSizing::Manual
+        } else {
+            TerminalSizing::FitWindow
+        };
+    }
+
+    /// Draws box drawing, block and braille characters procedurally instead of from the font, so
+    /// borders and gauges join up without gaps. Off by default.
+    pub fn geometric_glyphs(&mut self, value: bool) {
+        self.geometric_glyphs = value;
+    }
+
+    /// Draws cells matching the tileset as sprite tiles. Cells already on screen pick it up the
+    /// next time they change, call `clear()` on the terminal to redraw everything.
+    pub fn tileset(&mut self, tileset: Tileset) {
+        self.tileset = Some(tileset);
+    }
+
+    /// Draws an image over the cells of `area`, scaled to fill it. Place it after drawing the
+    /// frame, the image goes away by itself as soon as a later draw writes into any of its cells.
+    pub fn place_image(&mut self, area: Rect, image: Handle<Image>) {
+        self.images.push(TerminalImage {
+            area,
+            image,
+            entity: Entity::PLACEHOLDER,
+        });
+    }
+
+    /// Removes every image overlapping `area`.
+    pub fn clear_images(&mut self, area: Rect) {
+        let (stale, kept) = std::mem::take(&mut self.images)
+            .into_iter()
+            .partition(|image| image.area.intersects(area));
+        self.images = kept;
+        self.retire_images(stale);
+    }
+
+    /// Removes every image that a cell drawn since `batch_start` landed on, marking the drawn
+    /// cells once so each image is only checked against its own area.
+    fn clear_drawn_images(&mut self, batch_start: usize) {
+        let area = self.buffer.area;
+        let mut drawn = vec![false; area.area() as usize];
+        for (x, y, _) in &self.vcupdate[batch_start..] {
+            if area.contains(Position::new(*x, *y)) {
+                drawn[self.buffer.index_of(*x, *y)] = true;
+            }
+        }
+
+        let (stale, kept) = std::mem::take(&mut self.images)
+            .into_iter()
+            .partition(|image| {
+                let image_area = image.area.intersection(area);
+                image_area.area() > 0
+                    && image_area.rows().any(|row| {
+                        let start = self.buffer.index_of(row.x, row.y);
+                        drawn[start..start + row.width as usize].contains(&true)
+                    })
+            });
+        self.images = kept;
+        self.retire_images(stale);
+    }
+
+    fn retire_images(&mut self, stale: Vec<TerminalImage>) {
+        self.stale_images.extend(
+            stale
+                .into_iter()
+                .map(|image| image.entity)
+                .filter(|entity| *entity != Entity::PLACEHOLDER),
+        );
+    }
+
+    /// Sets the policy used to fit the grid into the window or parent node.
+    pub fn sizing(&mut self, sizing: TerminalSizing) {
+        self.sizing = sizing;
+    }
+
+    /// Limits the grid sizes FitWindow may pick, as (columns, rows).
+    pub fn grid_limits(&mut self, min: (u16, u16), max: (u16, u16)) {
+        self.min_grid = (min.0.max(1), min.1.max(1));
+        self.max_grid = (max.0.max(self.min_grid.0), max.1.max(self.min_grid.1));
+    }
+
+    /// Draws the terminal with an entity per cell or per row, see [`RenderMode`].
+    pub fn render_mode(&mut self, mode: RenderMode) {
+        self.render_mode = mode;
+    }
+
+    /// Records the measured size of one cell and the scale factor it was measured at, see
+    /// [`BevyBackend::snapped_cell_size`].
+    pub fn set_metrics(&mut self, measured_cell: Vec2, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+        self.cell_size = self.snapped_cell_size(measured_cell, scale_factor);
+    }
+
+    /// The cell size for a measured cell, snapped to whole physical pixels so glyphs line up
+    /// crisply on HiDPI displays. In [`RenderMode::Rows`] the width is kept at the font's advance
+    /// instead, since the glyphs of a row are laid out by the font and cells have to follow them.
+    pub fn snapped_cell_size(&self, measured_cell: Vec2, scale_factor: f32) -> Vec2 {
+        let snapped = (measured_cell * scale_factor).round().max(Vec2::ONE) / scale_factor;
+        match self.render_mode {
+            RenderMode::Cells => snapped,
+            RenderMode::Rows => Vec2::new(measured_cell.x, snapped.y),
+        }
+    }
+
+    /// Size of one cell in physical pixels.
+    pub fn physical_cell_size(&self) -> UVec2 {
+        (self.cell_size * self.scale_factor).round().as_uvec2()
+    }
+
+    /// Size of the whole grid in physical pixels.
+    pub fn physical_grid_size(&self) -> UVec2 {
+        self.physical_cell_size() * UVec2::new(self.width as u32, self.height as u32)
+    }
+
+    /// Logical position of the top left corner of a cell, relative to the area the terminal was
+    /// fitted to.
+    pub fn cell_position(&self, x: u16, y: u16) -> Vec2 {
+        self.grid_offset + Vec2::new(x as f32, y as f32) * self.cell_size
+    }
+
+    /// Rounds a logical position down to the physical pixel grid.
+    fn snap(&self, logical: Vec2) -> Vec2 {
+        (logical * self.scale_factor).floor() / self.scale_factor
+    }
+
+    /// Applies the sizing policy for an area of the given logical size, using the last measured
+    /// cell size. Returns true if the cells have to be rebuilt.
+    pub fn fit_to_area(&mut self, area: Vec2) -> bool {
+        let cell_size = self.cell_size;
+        if cell_size.x <= 0.0 || cell_size.y <= 0.0 || area.x <= 0.0 || area.y <= 0.0 {
+            // layout hasn't run yet
+            return false;
+        }
+
+        let old_offset = self.grid_offset;
+
+        match self.sizing {
+            TerminalSizing::Manual => false,
+            TerminalSizing::FitWindow => {
+                let new_wid =
+                    ((area.x / cell_size.x) as u16).clamp(self.min_grid.0, self.max_grid.0);
+                let new_hei =
+                    ((area.y / cell_size.y) as u16).clamp(self.min_grid.1, self.max_grid.1);
+                let grid = Vec2::new(new_wid as f32, new_hei as f32);
+                self.grid_offset = self.snap(((area - grid * cell_size) / 2.0).max(Vec2::ZERO));
+
+                let resized = (new_wid, new_hei) != (self.width, self.height);
+                if resized {
+                    self.resize(new_wid, new_hei);
+                }
+                resized || old_offset != self.grid_offset
+            }
+            TerminalSizing::ScaleFont | TerminalSizing::IntegerScale => {
+                let base_cell = cell_size / self.font_scale;
+                let grid = Vec2::new(self.width as f32, self.height as f32);
+
+                let mut scale = (area / (grid * base_cell)).min_element();
+                if self.sizing == TerminalSizing::IntegerScale {
+                    scale = scale.floor().max(1.0);
+                }
+                self.grid_offset =
+                    self.snap(((area - grid * base_cell * scale) / 2.0).max(Vec2::ZERO));
+
+                let rescaled = (scale - self.font_scale).abs() > f32::EPSILON;
+                if rescaled {
+                    self.font_scale = scale;
+                }
+                rescaled || old_offset != self.grid_offset
+            }
+        }
+    }
+
+    /// The screen as plain text, see [`export_text`].
+    pub fn snapshot_text(&self) -> String {
+        export_text(&self.buffer, self.buffer.area)
+    }
+
+    /// The screen with ANSI escape codes, see [`export_ansi`].
+    pub fn snapshot_ansi(&self) -> String {
+        export_ansi(&self.buffer, self.buffer.area)
+    }
+
+    /// The screen as a standalone HTML page, see [`export_html`].
+    pub fn snapshot_html(&self) -> String {
+        export_html(&self.buffer, self.buffer.area)
+    }
+
+    /// Starts recording every draw as an asciinema cast, beginning with the current screen.
+    pub fn start_recording(&mut self) {
+        self.recorder = Some(CastRecorder::new(&self.buffer));
+    }
+
+    /// Stops the running recording and hands it over, ready to be saved.
+    pub fn stop_recording(&mut self) -> Option<CastRecorder> {
+        self.recorder.take()
+    }
+
+    /// Writes every draw to a real terminal as well, starting with the current screen.
+    pub fn mirror_to(&mut self, mirror: TerminalMirror) {
+        mirror.write("\x1b[0m\x1b[H\x1b[2J");
+        mirror.write(&ansi_batch(&screen_cells(&self.buffer)));
+        mirror.flush();
+        self.mirror = Some(mirror);
+    }
+
+    pub fn stop_mirroring(&mut self) {
+        self.mirror = None;
+    }
+
+    /// Sends raw ANSI output to the recording and the mirror, if there are any.
+    fn emit(&mut self, data: &str) {
+        if let Some(recorder) = &mut self.recorder {
+            recorder.output(data.to_string());
+        }
+        if let Some(mirror) = &self.mirror {
+            mirror.write(data);
+        }
+    }
+
+    /// Places the terminal inside the Bevy UI node its entity is a child of. The grid follows the
+    /// computed size of that node and the window resolution is never touched.
+    pub fn embedded(&mut self, value: bool) {
+        self.embedded = value;
     }
 }
 
@@ -127,26 +503,38 @@ impl Backend for BevyBackend {
     where
         I: Iterator<Item = (u16, u16, &'a Cell)>,
     {
+        let batch_start = self.vcupdate.len();
         for (x, y, c) in content {
-            if !c.skip {self.vcupdate.push((x, y, c.clone()));
-                let cell = self.buffer.get_mut(x, y);
-                *cell = c.clone();
-    
-                // println!("{} {}", x, y);
-                //  println!("{:?}", c);}
-            
-
-          
-        }}
+            // cells drawn for a bigger grid, like a threaded app's draw from before a resize
+            if c.skip || !self.buffer.area.contains(Position { x, y }) {
+                continue;
+            }
+            self.vcupdate.push((x, y, c.clone()));
+            let cell = self.buffer.get_mut(x, y);
+            *cell = c.clone();
+        }
+        if !self.images.is_empty() {
+            self.clear_drawn_images(batch_start);
+        }
+        let batch = &self.vcupdate[batch_start..];
+        if !batch.is_empty() && (self.recorder.is_some() || self.mirror.is_some()) {
+            let data = ansi_batch(batch);
+            self.emit(&data);
+        }
         Ok(())
-    
     }
     fn hide_cursor(&mut self) -> Result<(), io::Error> {
+        if self.cursor {
+            self.emit("\x1b[?25l");
+        }
         self.cursor = false;
         Ok(())
     }
 
     fn show_cursor(&mut self) -> Result<(), io::Error> {
+        if !self.cursor {
+            self.emit("\x1b[?25h");
+        }
         self.cursor = true;
         Ok(())
     }
@@ -162,6 +550,7 @@ impl Backend for BevyBackend {
 
     fn clear(&mut self) -> Result<(), io::Error> {
         self.buffer.reset();
+        self.emit("\x1b[0m\x1b[H\x1b[2J");
         Ok(())
     }
 
@@ -187,6 +576,16 @@ impl Backend for BevyBackend {
                 self.buffer.content[index..=line_end_index].fill(Cell::default());
             }
         }
+        // ED or EL from the cursor, which the mirror and recording only know once it's moved there
+        let erase = match clear_type {
+            ClearType::All => return Ok(()),
+            ClearType::AfterCursor => "J",
+            ClearType::BeforeCursor => "1J",
+            ClearType::CurrentLine => "2K",
+            ClearType::UntilNewLine => "K",
+        };
+        let (x, y) = self.cursor_pos;
+        self.emit(&format!("\x1b[0m\x1b[{};{}H\x1b[{}", y + 1, x + 1, erase));
         Ok(())
     }
 
@@ -235,10 +634,10 @@ impl Backend for BevyBackend {
     }
 
     fn window_size(&mut self) -> Result<WindowSize, io::Error> {
-        // Some arbitrary window pixel size, probably doesn't need much testing.
+        let grid = self.physical_grid_size();
         let window_pixel_size: Size = Size {
-            width: 600,
-            height: 480,
+            width: grid.x.min(u16::MAX as u32) as u16,
+            height: grid.y.min(u16::MAX as u32) as u16,
         };
         Ok(WindowSize {
             columns_rows: (self.width, self.height).into(),
@@ -247,6 +646,102 @@ impl Backend for BevyBackend {
     }
 
     fn flush(&mut self) -> Result<(), io::Error> {
+        if let Some(mirror) = &self.mirror {
+            if self.cursor {
+                let (x, y) = self.cursor_pos;
+                mirror.write(&format!("\x1b[{};{}H", y + 1, x + 1));
+            }
+            mirror.flush();
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::*;
+
+    /// Bytes written to a mirror, readable after the mirror moved into the backend.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        fn take(&self) -> String {
+            String::from_utf8(std::mem::take(&mut *self.0.lock().unwrap())).unwrap()
+        }
+    }
+
+    #[test]
+    fn clearing_regions_is_mirrored_from_the_cursor() {
+        let mut backend = BevyBackend::default();
+        backend.resize(4, 3);
+        let out = Shared::default();
+        backend.mirror_to(TerminalMirror::new(out.clone()));
+        out.take();
+
+        backend.set_cursor(2, 1).unwrap();
+        for (clear_type, erase) in [
+            (ClearType::AfterCursor, "\x1b[J"),
+            (ClearType::BeforeCursor, "\x1b[1J"),
+            (ClearType::CurrentLine, "\x1b[2K"),
+            (ClearType::UntilNewLine, "\x1b[K"),
+        ] {
+            backend.clear_region(clear_type).unwrap();
+            assert_eq!(
+                out.take(),
+                format!("\x1b[0m\x1b[2;3H{erase}"),
+                "{clear_type:?}"
+            );
+        }
+        backend.clear_region(ClearType::All).unwrap();
+        assert_eq!(out.take(), "\x1b[0m\x1b[H\x1b[2J");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn entity_map_matches_cell_entity() {
+        let backend = BevyBackend {
+            cell_columns: 3,
+            cell_entities: (0..6).map(Entity::from_raw).collect(),
+            ..BevyBackend::default()
+        };
+
+        let map = backend.entity_map();
+        assert_eq!(map.len(), 6);
+        for ((x, y), entity) in map {
+            assert_eq!(backend.cell_entity(x, y), Some(entity));
+        }
+    }
+
+    #[test]
+    fn drawing_over_an_image_removes_only_that_image() {
+        let mut backend = BevyBackend::default();
+        backend.resize(10, 4);
+        backend.place_image(Rect::new(0, 0, 3, 2), Handle::default());
+        backend.place_image(Rect::new(5, 1, 3, 3), Handle::default());
+
+        let cell = Cell::default();
+        backend
+            .draw([(9, 0, &cell), (4, 3, &cell), (6, 3, &cell)].into_iter())
+            .unwrap();
+
+        assert_eq!(backend.images.len(), 1);
+        assert_eq!(backend.images[0].area, Rect::new(0, 0, 3, 2));
+
+        backend.draw([(2, 1, &cell)].into_iter()).unwrap();
+        assert!(backend.images.is_empty());
+    }
+}
@@ -0,0 +1,634 @@
+//! Bitmap fonts for pixel perfect terminals. BDF and PSF (v1 and v2) fonts and 16x16 CP437
+//! tilesheet PNGs are loaded into a [`BitmapFont`], a texture atlas with one tile per glyph.
+//!
+//! Pass a path ending in `.bdf`, `.psf`, `.psfu` or `.cp437.png` to [`crate::BevyBackend::new`]
+//! instead of a TTF and the terminal will draw that style from the bitmap font. The cell size is
+//! taken from the glyph size of the font.
+
+use std::{error::Error, fmt};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::{CompressedImageFormats, ImageSampler, ImageType},
+    },
+    utils::{BoxedFuture, HashMap},
+};
+
+/// Code page 437 as unicode, in code point order. Used for tilesheets and for PSF fonts that
+/// come without a unicode table.
+pub(crate) const CP437: [char; 256] = [
+    '\0', '☺', '☻', '♥', '♦', '♣', '♠', '•', '◘', '○', '◙', '♂', '♀', '♪', '♫', '☼', //
+    '►', '◄', '↕', '‼', '¶', '§', '▬', '↨', '↑', '↓', '→', '←', '∟', '↔', '▲', '▼', //
+    ' ', '!', '"', '#', '$', '%', '&', '\'', '(', ')', '*', '+', ',', '-', '.', '/', //
+    '0', '1', '2', '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?', //
+    '@', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J', 'K', 'L', 'M', 'N', 'O', //
+    'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z', '[', '\\', ']', '^', '_', //
+    '`', 'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', //
+    'p', 'q', 'r', 's', 't', 'u', 'v', 'w', 'x', 'y', 'z', '{', '|', '}', '~', '⌂', //
+    'Ç', 'ü', 'é', 'â', 'ä', 'à', 'å', 'ç', 'ê', 'ë', 'è', 'ï', 'î', 'ì', 'Ä', 'Å', //
+    'É', 'æ', 'Æ', 'ô', 'ö', 'ò', 'û', 'ù', 'ÿ', 'Ö', 'Ü', '¢', '£', '¥', '₧', 'ƒ', //
+    'á', 'í', 'ó', 'ú', 'ñ', 'Ñ', 'ª', 'º', '¿', '⌐', '¬', '½', '¼', '¡', '«', '»', //
+    '░', '▒', '▓', '│', '┤', '╡', '╢', '╖', '╕', '╣', '║', '╗', '╝', '╜', '╛', '┐', //
+    '└', '┴', '┬', '├', '─', '┼', '╞', '╟', '╚', '╔', '╩', '╦', '╠', '═', '╬', '╧', //
+    '╨', '╤', '╥', '╙', '╘', '╒', '╓', '╫', '╪', '┘', '┌', '█', '▄', '▌', '▐', '▀', //
+    'α', 'ß', 'Γ', 'π', 'Σ', 'σ', 'µ', 'τ', 'Φ', 'Θ', 'Ω', 'δ', '∞', 'φ', 'ε', '∩', //
+    '≡', '±', '≥', '≤', '⌠', '⌡', '÷', '≈', '°', '∙', '·', '√', 'ⁿ', '²', '■', '\u{a0}', //
+];
+
+/// Returns true if the path names a font that should be loaded as a [`BitmapFont`].
+pub(crate) fn is_bitmap_font_path(path: &str) -> bool {
+    let path = path.to_lowercase();
+    [".bdf", ".psf", ".psfu", ".cp437.png"]
+        .iter()
+        .any(|ext| path.ends_with(ext))
+}
+
+/// A monospace bitmap font packed into a texture atlas, white glyphs on a transparent background
+/// so they can be tinted with the cell foreground.
+#[derive(Asset, TypePath, Debug, Clone)]
+pub struct BitmapFont {
+    /// Size of every glyph in pixels, which is also the size of a cell at scale 1
+    pub glyph_size: UVec2,
+    /// Atlas tile index of each character
+    pub glyphs: HashMap<char, usize>,
+    /// Alpha coverage of each tile, glyph_size.x * glyph_size.y bytes row by row
+    pub coverage: Vec<Vec<u8>>,
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+}
+
+impl BitmapFont {
+    /// Atlas tile index for the first character of a cell symbol.
+    pub fn glyph(&self, symbol: &str) -> Option<usize> {
+        let mut chars = symbol.chars();
+        let c = chars.next()?;
+        self.glyphs.get(&c).copied()
+    }
+}
+
+/// Equally sized glyph coverage bitmaps that get packed into a single atlas.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct GlyphSheet {
+    pub glyph_size: UVec2,
+    pub glyphs: HashMap<char, usize>,
+    pub coverage: Vec<Vec<u8>>,
+}
+
+impl GlyphSheet {
+    pub fn new(glyph_size: UVec2) -> Self {
+        GlyphSheet {
+            glyph_size,
+            ..default()
+        }
+    }
+
+    /// Adds a tile and maps the given characters to it. Characters that already have a tile keep
+    /// their first one.
+    pub fn push(&mut self, chars: &[char], coverage: Vec<u8>) -> usize {
+        let index = self.coverage.len();
+        self.coverage.push(coverage);
+        for c in chars {
+            self.glyphs.entry(*c).or_insert(index);
+        }
+        index
+    }
+
+    /// Packs the tiles into rows of 16 in an RGBA image with a matching atlas layout.
+    pub fn to_atlas(&self) -> (Image, TextureAtlasLayout) {
+        let columns = 16;
+        let rows = self.coverage.len().max(1).div_ceil(columns);
+        let (gw, gh) = (self.glyph_size.x as usize, self.glyph_size.y as usize);
+        let (width, height) = (columns * gw, rows * gh);
+
+        let mut data = vec![0u8; width * height * 4];
+        for (index, tile) in self.coverage.iter().enumerate() {
+            let (tx, ty) = ((index % columns) * gw, (index / columns) * gh);
+            for y in 0..gh {
+                for x in 0..gw {
+                    let alpha = tile.get(y * gw + x).copied().unwrap_or(0);
+                    let offset = ((ty + y) * width + tx + x) * 4;
+                    data[offset..offset + 4].copy_from_slice(&[255, 255, 255, alpha]);
+                }
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: width as u32,
+                height: height as u32,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        image.sampler = ImageSampler::nearest();
+
+        let layout = TextureAtlasLayout::from_grid(
+            Vec2::new(gw as f32, gh as f32),
+            columns,
+            rows,
+            None,
+            None,
+        );
+        (image, layout)
+    }
+}
+
+/// Errors that can happen while loading a [`BitmapFont`].
+#[derive(Debug)]
+pub enum BitmapFontError {
+    Io(std::io::Error),
+    Parse(String),
+    Image(String),
+}
+
+impl fmt::Display for BitmapFontError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitmapFontError::Io(e) => write!(f, "could not read bitmap font: {e}"),
+            BitmapFontError::Parse(e) => write!(f, "invalid bitmap font: {e}"),
+            BitmapFontError::Image(e) => write!(f, "invalid font tilesheet: {e}"),
+        }
+    }
+}
+
+impl Error for BitmapFontError {}
+
+impl From<std::io::Error> for BitmapFontError {
+    fn from(e: std::io::Error) -> Self {
+        BitmapFontError::Io(e)
+    }
+}
+
+/// Loads `.bdf`, `.psf`, `.psfu` and `.cp437.png` files as [`BitmapFont`]s.
+#[derive(Default)]
+pub struct BitmapFontLoader;
+
+impl AssetLoader for BitmapFontLoader {
+    type Asset = BitmapFont;
+    type Settings = ();
+    type Error = BitmapFontError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            let path = load_context.path().to_string_lossy().to_lowercase();
+            let sheet = if path.ends_with(".bdf") {
+                parse_bdf(&String::from_utf8_lossy(&bytes))?
+            } else if path.ends_with(".cp437.png") {
+                parse_cp437_sheet(&bytes)?
+            } else {
+                parse_psf(&bytes)?
+            };
+
+            let (image, layout) = sheet.to_atlas();
+            let image = load_context.add_labeled_asset("atlas".to_string(), image);
+            let layout = load_context.add_labeled_asset("layout".to_string(), layout);
+
+            Ok(BitmapFont {
+                glyph_size: sheet.glyph_size,
+                glyphs: sheet.glyphs,
+                coverage: sheet.coverage,
+                image,
+                layout,
+            })
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["bdf", "psf", "psfu", "cp437.png"]
+    }
+}
+
+fn parse_numbers(rest: &str) -> Vec<i32> {
+    rest.split_whitespace()
+        .filter_map(|n| n.parse().ok())
+        .collect()
+}
+
+/// Largest glyph side a BDF font may declare, anything bigger is a broken or hostile file.
+const MAX_BDF_GLYPH: i32 = 1024;
+
+/// Checks a BDF box has a sane size, offsets are only checked where they're used.
+fn bdf_box(n: &[i32], min: i32) -> Option<(i32, i32, i32, i32)> {
+    let size = min..=MAX_BDF_GLYPH;
+    (n.len() == 4 && size.contains(&n[0]) && size.contains(&n[1])).then(|| (n[0], n[1], n[2], n[3]))
+}
+
+/// Parses a BDF font, placing every glyph on the font bounding box so they all share a baseline.
+pub(crate) fn parse_bdf(source: &str) -> Result<GlyphSheet, BitmapFontError> {
+    let mut lines = source.lines().map(str::trim);
+    let mut font_box = None;
+    let mut sheet = GlyphSheet::default();
+
+    while let Some(line) = lines.next() {
+        let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+        match keyword {
+            "FONTBOUNDINGBOX" => {
+                let (w, h, x, y) = bdf_box(&parse_numbers(rest), 1)
+                    .ok_or_else(|| BitmapFontError::Parse("bad FONTBOUNDINGBOX".to_string()))?;
+                font_box = Some((w, h, x, y));
+                sheet.glyph_size = UVec2::new(w as u32, h as u32);
+            }
+            "STARTCHAR" => {
+                let (fw, fh, fx, fy) = font_box.ok_or_else(|| {
+                    BitmapFontError::Parse("STARTCHAR before FONTBOUNDINGBOX".to_string())
+                })?;
+                let mut encoding = -1;
+                let mut bbx = (fw, fh, fx, fy);
+                let mut coverage = vec![0u8; (fw * fh) as usize];
+
+                for line in lines.by_ref() {
+                    let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+                    match keyword {
+                        "ENCODING" => {
+                            encoding = parse_numbers(rest).first().copied().unwrap_or(-1);
+                        }
+                        "BBX" => {
+                            bbx = bdf_box(&parse_numbers(rest), 0)
+                                .ok_or_else(|| BitmapFontError::Parse(format!("bad BBX {rest}")))?;
+                        }
+                        "BITMAP" => break,
+                        _ => {}
+                    }
+                }
+
+                let (bw, bh, bx, by) = bbx;
+                let offsets = bx.checked_sub(fx).zip(
+                    fh.checked_add(fy)
+                        .zip(bh.checked_add(by))
+                        .and_then(|(font_top, glyph_top)| font_top.checked_sub(glyph_top)),
+                );
+                let Some((left, top)) = offsets else {
+                    return Err(BitmapFontError::Parse(format!(
+                        "glyph {rest} is placed out of range"
+                    )));
+                };
+                for row in 0..bh {
+                    let digits: Vec<u32> = lines
+                        .next()
+                        .unwrap_or("")
+                        .chars()
+                        .map(|d| d.to_digit(16).unwrap_or(0))
+                        .collect();
+                    for col in 0..bw.min(digits.len() as i32 * 4) {
+                        if digits[col as usize / 4] >> (3 - col % 4) & 1 == 1 {
+                            let (Some(x), Some(y)) = (left.checked_add(col), top.checked_add(row))
+                            else {
+                                continue;
+                            };
+                            if (0..fw).contains(&x) && (0..fh).contains(&y) {
+                                coverage[(y * fw + x) as usize] = 255;
+                            }
+                        }
+                    }
+                }
+                for line in lines.by_ref() {
+                    if line == "ENDCHAR" {
+                        break;
+                    }
+                }
+
+                if let Some(c) = u32::try_from(encoding).ok().and_then(char::from_u32) {
+                    sheet.push(&[c], coverage);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if font_box.is_none() || sheet.coverage.is_empty() {
+        return Err(BitmapFontError::Parse("no glyphs in BDF font".to_string()));
+    }
+    Ok(sheet)
+}
+
+/// Parses a PSF1 or PSF2 font, using its unicode table if it has one and CP437 otherwise.
+pub(crate) fn parse_psf(bytes: &[u8]) -> Result<GlyphSheet, BitmapFontError> {
+    let truncated = || BitmapFontError::Parse("truncated PSF font".to_string());
+    let read_u32 = |at: usize| -> Result<u32, BitmapFontError> {
+        bytes
+            .get(at..at + 4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .ok_or_else(truncated)
+    };
+
+    let (width, height, count, glyph_bytes, header_len, unicode_table) =
+        if bytes.starts_with(&[0x36, 0x04]) {
+            let mode = *bytes.get(2).ok_or_else(truncated)?;
+            let charsize = *bytes.get(3).ok_or_else(truncated)? as usize;
+            let count = if mode & 0x01 != 0 { 512 } else { 256 };
+            (8, charsize, count, charsize, 4, mode & 0x06 != 0)
+        } else if bytes.starts_with(&[0x72, 0xb5, 0x4a, 0x86]) {
+            let header_len = read_u32(8)? as usize;
+            let flags = read_u32(12)?;
+            let count = read_u32(16)? as usize;
+            let glyph_bytes = read_u32(20)? as usize;
+            let height = read_u32(24)? as usize;
+            let width = read_u32(28)? as usize;
+            (
+                width,
+                height,
+                count,
+                glyph_bytes,
+                header_len,
+                flags & 1 != 0,
+            )
+        } else {
+            return Err(BitmapFontError::Parse("not a PSF font".to_string()));
+        };
+
+    if width == 0 || height == 0 {
+        return Err(BitmapFontError::Parse("empty PSF glyphs".to_string()));
+    }
+
+    // every row of a glyph starts on a whole byte, so a glyph can't be smaller than its rows
+    let row_bytes = width.div_ceil(8);
+    let min_glyph_bytes = row_bytes
+        .checked_mul(height)
+        .ok_or_else(|| BitmapFontError::Parse("PSF glyphs are too large".to_string()))?;
+    if glyph_bytes == 0 || glyph_bytes < min_glyph_bytes {
+        return Err(BitmapFontError::Parse(format!(
+            "PSF glyphs of {width}x{height} don't fit in {glyph_bytes} bytes"
+        )));
+    }
+    let data_end = count
+        .checked_mul(glyph_bytes)
+        .and_then(|len| len.checked_add(header_len))
+        .ok_or_else(truncated)?;
+    let glyph_data = bytes.get(header_len..data_end).ok_or_else(truncated)?;
+
+    let mut sheet = GlyphSheet::new(UVec2::new(width as u32, height as u32));
+    for glyph in glyph_data.chunks(glyph_bytes) {
+        let mut coverage = vec![0u8; width * height];
+        for y in 0..height {
+            for x in 0..width {
+                let byte = glyph.get(y * row_bytes + x / 8).copied().unwrap_or(0);
+                if byte & (0x80 >> (x % 8)) != 0 {
+                    coverage[y * width + x] = 255;
+                }
+            }
+        }
+        sheet.coverage.push(coverage);
+    }
+
+    let table = &bytes[data_end..];
+    if unicode_table && bytes[0] == 0x36 {
+        // PSF1: u16 code points per glyph, 0xFFFE starts a sequence, 0xFFFF ends the glyph
+        let mut index = 0;
+        let mut in_sequence = false;
+        for pair in table.chunks_exact(2) {
+            match u16::from_le_bytes([pair[0], pair[1]]) {
+                0xFFFF => {
+                    index += 1;
+                    in_sequence = false;
+                }
+                0xFFFE => in_sequence = true,
+                point if !in_sequence => {
+                    if let Some(c) = char::from_u32(point as u32) {
+                        sheet.glyphs.entry(c).or_insert(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+    } else if unicode_table {
+        // PSF2: utf-8 per glyph, 0xFE starts a sequence, 0xFF ends the glyph
+        for (index, entry) in table.split(|b| *b == 0xFF).take(count).enumerate() {
+            let singles = entry.split(|b| *b == 0xFE).next().unwrap_or(&[]);
+            for c in String::from_utf8_lossy(singles).chars() {
+                sheet.glyphs.entry(c).or_insert(index);
+            }
+        }
+    } else {
+        for (index, c) in CP437.iter().enumerate().take(count) {
+            sheet.glyphs.entry(*c).or_insert(index);
+        }
+    }
+
+    Ok(sheet)
+}
+
+/// Parses a 16x16 tilesheet PNG laid out in CP437 order. Glyphs can be drawn white or coloured
+/// on black, transparent or magenta, the brightest channel is used as coverage.
+pub(crate) fn parse_cp437_sheet(bytes: &[u8]) -> Result<GlyphSheet, BitmapFontError> {
+    let image = Image::from_buffer(
+        bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::nearest(),
+        RenderAssetUsages::default(),
+    )
+    .map_err(|e| BitmapFontError::Image(e.to_string()))?;
+    let image = image
+        .convert(TextureFormat::Rgba8UnormSrgb)
+        .ok_or_else(|| BitmapFontError::Image("unsupported pixel format".to_string()))?;
+
+    let size = image.size();
+    if size.x < 16 || size.y < 16 {
+        return Err(BitmapFontError::Image("tilesheet is too small".to_string()));
+    }
+    let glyph_size = size / 16;
+    let (gw, gh) = (glyph_size.x as usize, glyph_size.y as usize);
+
+    let mut sheet = GlyphSheet::new(glyph_size);
+    for (index, c) in CP437.iter().enumerate() {
+        let (tx, ty) = ((index % 16) * gw, (index / 16) * gh);
+        let mut coverage = vec![0u8; gw * gh];
+        for y in 0..gh {
+            for x in 0..gw {
+                let offset = ((ty + y) * size.x as usize + tx + x) * 4;
+                let [r, g, b, a] = [
+                    image.data[offset],
+                    image.data[offset + 1],
+                    image.data[offset + 2],
+                    image.data[offset + 3],
+                ];
+                let magenta = r == 255 && g == 0 && b == 255;
+                if !magenta {
+                    coverage[y * gw + x] = ((r.max(g).max(b) as u16 * a as u16) / 255) as u8;
+                }
+            }
+        }
+        sheet.push(&[*c], coverage);
+    }
+
+    Ok(sheet)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A PSF2 header for 8 pixel wide glyphs, followed by nothing.
+    fn psf2_header(flags: u32, count: u32, glyph_bytes: u32, height: u32) -> Vec<u8> {
+        let mut bytes = vec![0x72, 0xb5, 0x4a, 0x86];
+        for field in [0, 32, flags, count, glyph_bytes, height, 8] {
+            bytes.extend_from_slice(&u32::to_le_bytes(field));
+        }
+        bytes
+    }
+
+    #[test]
+    fn psf1_uses_cp437() {
+        let mut bytes = vec![0x36, 0x04, 0x00, 2];
+        for index in 0..256 {
+            bytes.extend_from_slice(&[index as u8, 0x80]);
+        }
+
+        let sheet = parse_psf(&bytes).unwrap();
+        assert_eq!(sheet.glyph_size, UVec2::new(8, 2));
+        assert_eq!(sheet.coverage.len(), 256);
+        assert_eq!(sheet.glyphs[&'A'], 65);
+        // 'A' is 0b0100_0001 on the first row, the second row is the leftmost pixel
+        let a = &sheet.coverage[65];
+        assert_eq!(&a[..8], &[0, 255, 0, 0, 0, 0, 0, 255]);
+        assert_eq!(&a[8..], &[255, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn psf2_reads_the_unicode_table() {
+        let mut bytes = psf2_header(1, 2, 2, 2);
+        bytes.extend_from_slice(&[0xff, 0x00, 0x00, 0xff]);
+        bytes.extend_from_slice("a\u{e9}".as_bytes());
+        bytes.push(0xff);
+        bytes.extend_from_slice(b"b\xfeb\xcc\x81\xff");
+
+        let sheet = parse_psf(&bytes).unwrap();
+        assert_eq!(sheet.coverage.len(), 2);
+        assert_eq!(sheet.glyphs[&'a'], 0);
+        assert_eq!(sheet.glyphs[&'\u{e9}'], 0);
+        assert_eq!(sheet.glyphs[&'b'], 1);
+        // the combining sequence after 0xFE is not a single character
+        assert!(!sheet.glyphs.contains_key(&'\u{301}'));
+        assert_eq!(sheet.coverage[0][..8], [255; 8]);
+        assert_eq!(sheet.coverage[1][8..], [255; 8]);
+    }
+
+    #[test]
+    fn truncated_psf_is_an_error() {
+        assert!(matches!(
+            parse_psf(&[0x36, 0x04]),
+            Err(BitmapFontError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_psf(&[0x36, 0x04, 0x00, 8, 0xff]),
+            Err(BitmapFontError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_psf(&psf2_header(0, 2, 2, 2)[..20]),
+            Err(BitmapFontError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_psf(&psf2_header(0, 2, 2, 2)),
+            Err(BitmapFontError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_psf(b"not a font"),
+            Err(BitmapFontError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn zero_and_oversized_psf_headers_are_errors() {
+        // no bytes per glyph
+        let mut bytes = psf2_header(0, 2, 0, 2);
+        bytes.extend_from_slice(&[0; 4]);
+        assert!(matches!(parse_psf(&bytes), Err(BitmapFontError::Parse(_))));
+
+        // glyphs smaller than their rows
+        let mut bytes = psf2_header(0, 2, 1, 2);
+        bytes.extend_from_slice(&[0; 4]);
+        assert!(matches!(parse_psf(&bytes), Err(BitmapFontError::Parse(_))));
+
+        // zero height
+        assert!(matches!(
+            parse_psf(&[0x36, 0x04, 0x00, 0]),
+            Err(BitmapFontError::Parse(_))
+        ));
+
+        // a count and glyph size that overflow
+        let bytes = psf2_header(0, u32::MAX, u32::MAX, 2);
+        assert!(matches!(parse_psf(&bytes), Err(BitmapFontError::Parse(_))));
+    }
+
+    #[test]
+    fn bdf_places_glyphs_on_the_font_box() {
+        let source = "\
+STARTFONT 2.1
+FONTBOUNDINGBOX 4 4 0 -1
+CHARS 1
+STARTCHAR A
+ENCODING 65
+BBX 2 2 1 0
+BITMAP
+C0
+40
+ENDCHAR
+ENDFONT
+";
+        let sheet = parse_bdf(source).unwrap();
+        assert_eq!(sheet.glyph_size, UVec2::new(4, 4));
+        assert_eq!(sheet.glyphs[&'A'], 0);
+        #[rustfmt::skip]
+        assert_eq!(sheet.coverage[0], [
+            0, 0, 0, 0,
+            0, 255, 255, 0,
+            0, 0, 255, 0,
+            0, 0, 0, 0,
+        ]);
+    }
+
+    #[test]
+    fn bdf_without_glyphs_is_an_error() {
+        assert!(matches!(
+            parse_bdf("STARTFONT 2.1\nENDFONT\n"),
+            Err(BitmapFontError::Parse(_))
+        ));
+        assert!(matches!(
+            parse_bdf("FONTBOUNDINGBOX 0 4 0 0\n"),
+            Err(BitmapFontError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn negative_oversized_and_overflowing_bdf_boxes_are_errors() {
+        let glyph = |font_box: &str, bbx: &str| {
+            format!(
+                "FONTBOUNDINGBOX {font_box}\nSTARTCHAR A\nENCODING 65\nBBX {bbx}\nBITMAP\n80\nENDCHAR\n"
+            )
+        };
+        for (font_box, bbx) in [
+            ("-4 -4 0 0", "1 1 0 0"),
+            ("100000 100000 0 0", "1 1 0 0"),
+            ("4 4 0 0", "-1 1 0 0"),
+            ("4 4 0 0", "1 100000 0 0"),
+            ("4 4 -1 0", "1 1 2147483647 0"),
+            ("4 4 0 2147483647", "1 1 0 0"),
+            ("4 4 0 0", "1 1 0 -2147483648"),
+        ] {
+            assert!(
+                matches!(
+                    parse_bdf(&glyph(font_box, bbx)),
+                    Err(BitmapFontError::Parse(_))
+                ),
+                "{font_box} / {bbx}"
+            );
+        }
+        assert!(parse_bdf(&glyph("4 4 0 0", "1 1 0 0")).is_ok());
+    }
+}
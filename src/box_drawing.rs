@@ -0,0 +1,582 @@
+//! Procedural glyphs for box drawing (U+2500 to U+257F), block elements (U+2580 to U+259F) and
+//! braille (U+2800 to U+28FF). Font glyphs for these rarely fill the cell exactly, which leaves
+//! gaps in borders, gauges and canvases, so they are drawn to the exact cell size instead.
+
+use bevy::prelude::*;
+
+use crate::bitmap_font::GlyphSheet;
+
+/// Returns true if the character is drawn procedurally rather than taken from the font.
+pub(crate) fn is_geometric_char(c: char) -> bool {
+    matches!(c, '\u{2500}'..='\u{259F}' | '\u{2800}'..='\u{28FF}')
+}
+
+/// Line weights of the up, right, down and left arms of U+2500 to U+257F, where 1 is light, 2 is
+/// heavy and 3 is double, and the number of dashes (0 for solid). Arcs and diagonals are drawn
+/// separately and are all zero here.
+const BOX_ARMS: [([u8; 4], u8); 128] = [
+    ([0, 1, 0, 1], 0),
+    ([0, 2, 0, 2], 0),
+    ([1, 0, 1, 0], 0),
+    ([2, 0, 2, 0], 0), // ─━│┃
+    ([0, 1, 0, 1], 3),
+    ([0, 2, 0, 2], 3),
+    ([1, 0, 1, 0], 3),
+    ([2, 0, 2, 0], 3), // ┄┅┆┇
+    ([0, 1, 0, 1], 4),
+    ([0, 2, 0, 2], 4),
+    ([1, 0, 1, 0], 4),
+    ([2, 0, 2, 0], 4), // ┈┉┊┋
+    ([0, 1, 1, 0], 0),
+    ([0, 2, 1, 0], 0),
+    ([0, 1, 2, 0], 0),
+    ([0, 2, 2, 0], 0), // ┌┍┎┏
+    ([0, 0, 1, 1], 0),
+    ([0, 0, 1, 2], 0),
+    ([0, 0, 2, 1], 0),
+    ([0, 0, 2, 2], 0), // ┐┑┒┓
+    ([1, 1, 0, 0], 0),
+    ([1, 2, 0, 0], 0),
+    ([2, 1, 0, 0], 0),
+    ([2, 2, 0, 0], 0), // └┕┖┗
+    ([1, 0, 0, 1], 0),
+    ([1, 0, 0, 2], 0),
+    ([2, 0, 0, 1], 0),
+    ([2, 0, 0, 2], 0), // ┘┙┚┛
+    ([1, 1, 1, 0], 0),
+    ([1, 2, 1, 0], 0),
+    ([2, 1, 1, 0], 0),
+    ([1, 1, 2, 0], 0), // ├┝┞┟
+    ([2, 1, 2, 0], 0),
+    ([2, 2, 1, 0], 0),
+    ([1, 2, 2, 0], 0),
+    ([2, 2, 2, 0], 0), // ┠┡┢┣
+    ([1, 0, 1, 1], 0),
+    ([1, 0, 1, 2], 0),
+    ([2, 0, 1, 1], 0),
+    ([1, 0, 2, 1], 0), // ┤┥┦┧
+    ([2, 0, 2, 1], 0),
+    ([2, 0, 1, 2], 0),
+    ([1, 0, 2, 2], 0),
+    ([2, 0, 2, 2], 0), // ┨┩┪┫
+    ([0, 1, 1, 1], 0),
+    ([0, 1, 1, 2], 0),
+    ([0, 2, 1, 1], 0),
+    ([0, 2, 1, 2], 0), // ┬┭┮┯
+    ([0, 1, 2, 1], 0),
+    ([0, 1, 2, 2], 0),
+    ([0, 2, 2, 1], 0),
+    ([0, 2, 2, 2], 0), // ┰┱┲┳
+    ([1, 1, 0, 1], 0),
+    ([1, 1, 0, 2], 0),
+    ([1, 2, 0, 1], 0),
+    ([1, 2, 0, 2], 0), // ┴┵┶┷
+    ([2, 1, 0, 1], 0),
+    ([2, 1, 0, 2], 0),
+    ([2, 2, 0, 1], 0),
+    ([2, 2, 0, 2], 0), // ┸┹┺┻
+    ([1, 1, 1, 1], 0),
+    ([1, 1, 1, 2], 0),
+    ([1, 2, 1, 1], 0),
+    ([1, 2, 1, 2], 0), // ┼┽┾┿
+    ([2, 1, 1, 1], 0),
+    ([1, 1, 2, 1], 0),
+    ([2, 1, 2, 1], 0),
+    ([2, 1, 1, 2], 0), // ╀╁╂╃
+    ([2, 2, 1, 1], 0),
+    ([1, 1, 2, 2], 0),
+    ([1, 2, 2, 1], 0),
+    ([2, 2, 1, 2], 0), // ╄╅╆╇
+    ([1, 2, 2, 2], 0),
+    ([2, 1, 2, 2], 0),
+    ([2, 2, 2, 1], 0),
+    ([2, 2, 2, 2], 0), // ╈╉╊╋
+    ([0, 1, 0, 1], 2),
+    ([0, 2, 0, 2], 2),
+    ([1, 0, 1, 0], 2),
+    ([2, 0, 2, 0], 2), // ╌╍╎╏
+    ([0, 3, 0, 3], 0),
+    ([3, 0, 3, 0], 0),
+    ([0, 3, 1, 0], 0),
+    ([0, 1, 3, 0], 0), // ═║╒╓
+    ([0, 3, 3, 0], 0),
+    ([0, 0, 1, 3], 0),
+    ([0, 0, 3, 1], 0),
+    ([0, 0, 3, 3], 0), // ╔╕╖╗
+    ([1, 3, 0, 0], 0),
+    ([3, 1, 0, 0], 0),
+    ([3, 3, 0, 0], 0),
+    ([1, 0, 0, 3], 0), // ╘╙╚╛
+    ([3, 0, 0, 1], 0),
+    ([3, 0, 0, 3], 0),
+    ([1, 3, 1, 0], 0),
+    ([3, 1, 3, 0], 0), // ╜╝╞╟
+    ([3, 3, 3, 0], 0),
+    ([1, 0, 1, 3], 0),
+    ([3, 0, 3, 1], 0),
+    ([3, 0, 3, 3], 0), // ╠╡╢╣
+    ([0, 3, 1, 3], 0),
+    ([0, 1, 3, 1], 0),
+    ([0, 3, 3, 3], 0),
+    ([1, 3, 0, 3], 0), // ╤╥╦╧
+    ([3, 1, 0, 1], 0),
+    ([3, 3, 0, 3], 0),
+    ([1, 3, 1, 3], 0),
+    ([3, 1, 3, 1], 0), // ╨╩╪╫
+    ([3, 3, 3, 3], 0),
+    ([0, 0, 0, 0], 0),
+    ([0, 0, 0, 0], 0),
+    ([0, 0, 0, 0], 0), // ╬╭╮╯
+    ([0, 0, 0, 0], 0),
+    ([0, 0, 0, 0], 0),
+    ([0, 0, 0, 0], 0),
+    ([0, 0, 0, 0], 0), // ╰╱╲╳
+    ([0, 0, 0, 1], 0),
+    ([1, 0, 0, 0], 0),
+    ([0, 1, 0, 0], 0),
+    ([0, 0, 1, 0], 0), // ╴╵╶╷
+    ([0, 0, 0, 2], 0),
+    ([2, 0, 0, 0], 0),
+    ([0, 2, 0, 0], 0),
+    ([0, 0, 2, 0], 0), // ╸╹╺╻
+    ([0, 2, 0, 1], 0),
+    ([1, 0, 2, 0], 0),
+    ([0, 1, 0, 2], 0),
+    ([2, 0, 1, 0], 0), // ╼╽╾╿
+];
+
+/// Coverage bitmap the size of one cell, with helpers for drawing into it.
+struct Canvas {
+    w: i32,
+    h: i32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(size: UVec2) -> Self {
+        Canvas {
+            w: size.x as i32,
+            h: size.y as i32,
+            pixels: vec![0; (size.x * size.y) as usize],
+        }
+    }
+
+    /// Fills the half open rectangle [x0, x1) x [y0, y1), clipped to the cell.
+    fn fill(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, alpha: u8) {
+        for y in y0.max(0)..y1.min(self.h) {
+            for x in x0.max(0)..x1.min(self.w) {
+                self.pixels[(y * self.w + x) as usize] = alpha;
+            }
+        }
+    }
+
+    /// Fills a rectangle given in fractions of the cell, rounding to whole pixels so neighbouring
+    /// cells meet exactly.
+    fn fill_fraction(&mut self, x0: f32, y0: f32, x1: f32, y1: f32, alpha: u8) {
+        let (w, h) = (self.w as f32, self.h as f32);
+        self.fill(
+            (x0 * w).round() as i32,
+            (y0 * h).round() as i32,
+            (x1 * w).round() as i32,
+            (y1 * h).round() as i32,
+            alpha,
+        );
+    }
+
+    fn plot(&mut self, x: i32, y: i32) {
+        self.fill(x, y, x + 1, y + 1, 255);
+    }
+}
+
+/// Thickness in pixels of a light and a heavy line.
+fn line_widths(size: UVec2) -> (i32, i32) {
+    let light = ((size.x.min(size.y) as f32 / 8.0).round() as i32).max(1);
+    let heavy = (light * 2).max(light + 1);
+    (light, heavy)
+}
+
+/// Start and end of a stroke of the given thickness centred on c.
+fn stroke(c: i32, thickness: i32) -> (i32, i32) {
+    let start = c - thickness / 2;
+    (start, start + thickness)
+}
+
+/// Draws the arms of one box drawing character. The two strokes of a double line sit one light
+/// line width either side of the centre line.
+fn draw_arms(canvas: &mut Canvas, arms: [u8; 4], dashes: u8, widths: (i32, i32)) {
+    let (light, heavy) = widths;
+    let (cx, cy) = (canvas.w / 2, canvas.h / 2);
+    let d = light;
+    let [up, right, down, left] = arms;
+    let thickness = |weight: u8| if weight == 2 { heavy } else { light };
+
+    if dashes > 0 {
+        let horizontal = right > 0;
+        let t = thickness(right.max(up));
+        let length = if horizontal { canvas.w } else { canvas.h };
+        let segment = length as f32 / dashes as f32;
+        for i in 0..dashes {
+            let start = (i as f32 * segment).round() as i32;
+            let end = start + (segment * 0.6).round().max(1.0) as i32;
+            if horizontal {
+                let (y0, y1) = stroke(cy, t);
+                canvas.fill(start, y0, end, y1, 255);
+            } else {
+                let (x0, x1) = stroke(cx, t);
+                canvas.fill(x0, start, x1, end, 255);
+            }
+        }
+        return;
+    }
+
+    // each arm runs from its cell edge to around the centre, where exactly it stops depends on
+    // the perpendicular arms it has to join
+    for vertical in [true, false] {
+        let (c_across, c_along, length) = if vertical {
+            (cx, cy, canvas.h)
+        } else {
+            (cy, cx, canvas.w)
+        };
+        let (first, second, perp_before, perp_after) = if vertical {
+            (up, down, left, right)
+        } else {
+            (left, right, up, down)
+        };
+        let perp_double = perp_before == 3 || perp_after == 3;
+        let perp_thickness = [perp_before, perp_after]
+            .iter()
+            .filter(|w| **w == 1 || **w == 2)
+            .map(|w| thickness(*w))
+            .max()
+            .unwrap_or(0);
+
+        for (weight, from_start, opposite) in [(first, true, second), (second, false, first)] {
+            if weight == 0 {
+                continue;
+            }
+            // strokes of a perpendicular double line on this arm's side and on the far side
+            let (near, far) = if from_start {
+                (c_along - d, c_along + d)
+            } else {
+                (c_along + d, c_along - d)
+            };
+
+            // (across centre, thickness, along position to stop at, thickness of what it joins)
+            let mut strokes = Vec::with_capacity(2);
+            if weight == 3 {
+                for (side, side_arm) in [(-1, perp_before), (1, perp_after)] {
+                    // a double side arm makes an inner corner, otherwise run to the far stroke
+                    let stop = if side_arm == 3 { near } else { far };
+                    strokes.push((c_across + side * d, light, stop, light));
+                }
+            } else if perp_double {
+                // hang off the near stroke, unless the line carries on through the other side
+                let stop = if opposite == 0 { near } else { far };
+                strokes.push((c_across, thickness(weight), stop, light));
+            } else {
+                let t = thickness(weight);
+                strokes.push((c_across, t, c_along, t.max(perp_thickness)));
+            }
+
+            for (across, t, stop, stop_t) in strokes {
+                let (a0, a1) = stroke(across, t);
+                let (b0, b1) = if from_start {
+                    (0, stroke(stop, stop_t).1)
+                } else {
+                    (stroke(stop, stop_t).0, length)
+                };
+                if vertical {
+                    canvas.fill(a0, b0, a1, b1, 255);
+                } else {
+                    canvas.fill(b0, a0, b1, a1, 255);
+                }
+            }
+        }
+    }
+}
+
+/// Rounded corner joining the middle of two cell edges, dx and dy point at the edges it runs to.
+fn draw_arc(canvas: &mut Canvas, dx: i32, dy: i32, light: i32) {
+    let (cx, cy) = (canvas.w / 2, canvas.h / 2);
+    let radius = (canvas.w.min(canvas.h) / 2) as f32;
+    let (x0, x1) = stroke(cx, light);
+    let (y0, y1) = stroke(cy, light);
+    let centre = Vec2::new(
+        (x0 + x1) as f32 / 2.0 + dx as f32 * radius,
+        (y0 + y1) as f32 / 2.0 + dy as f32 * radius,
+    );
+
+    for y in 0..canvas.h {
+        for x in 0..canvas.w {
+            let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+            let towards = p - centre;
+            let in_quadrant = towards.x * dx as f32 <= 0.0 && towards.y * dy as f32 <= 0.0;
+            if in_quadrant && (towards.length() - radius).abs() <= light as f32 / 2.0 {
+                canvas.plot(x, y);
+            }
+        }
+    }
+
+    // straight runs from the end of the arc to the cell edges
+    let r = radius as i32;
+    if dx > 0 {
+        canvas.fill(cx + r, y0, canvas.w, y1, 255);
+    } else {
+        canvas.fill(0, y0, cx - r + 1, y1, 255);
+    }
+    if dy > 0 {
+        canvas.fill(x0, cy + r, x1, canvas.h, 255);
+    } else {
+        canvas.fill(x0, 0, x1, cy - r + 1, 255);
+    }
+}
+
+/// Diagonal from corner to corner, rising if `rising` is true.
+fn draw_diagonal(canvas: &mut Canvas, rising: bool, light: i32) {
+    let (w, h) = (canvas.w as f32, canvas.h as f32);
+    let length = (w * w + h * h).sqrt();
+    for y in 0..canvas.h {
+        for x in 0..canvas.w {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let distance = if rising {
+                (h * px + w * py - w * h).abs() / length
+            } else {
+                (h * px - w * py).abs() / length
+            };
+            if distance <= light as f32 / 2.0 + 0.25 {
+                canvas.plot(x, y);
+            }
+        }
+    }
+}
+
+fn draw_block(canvas: &mut Canvas, c: char) {
+    let eighth = |n: u32| n as f32 / 8.0;
+    match c {
+        '▀' => canvas.fill_fraction(0.0, 0.0, 1.0, 0.5, 255),
+        '\u{2581}'..='\u{2588}' => {
+            let n = c as u32 - 0x2580;
+            canvas.fill_fraction(0.0, 1.0 - eighth(n), 1.0, 1.0, 255);
+        }
+        '\u{2589}'..='\u{258F}' => {
+            let n = 0x2590 - c as u32;
+            canvas.fill_fraction(0.0, 0.0, eighth(n), 1.0, 255);
+        }
+        '▐' => canvas.fill_fraction(0.5, 0.0, 1.0, 1.0, 255),
+        '░' => canvas.fill_fraction(0.0, 0.0, 1.0, 1.0, 64),
+        '▒' => canvas.fill_fraction(0.0, 0.0, 1.0, 1.0, 128),
+        '▓' => canvas.fill_fraction(0.0, 0.0, 1.0, 1.0, 192),
+        '▔' => canvas.fill_fraction(0.0, 0.0, 1.0, eighth(1), 255),
+        '▕' => canvas.fill_fraction(1.0 - eighth(1), 0.0, 1.0, 1.0, 255),
+        _ => {
+            // quadrants, as upper left, upper right, lower left, lower right
+            let quadrants: [bool; 4] = match c {
+                '▖' => [false, false, true, false],
+                '▗' => [false, false, false, true],
+                '▘' => [true, false, false, false],
+                '▙' => [true, false, true, true],
+                '▚' => [true, false, false, true],
+                '▛' => [true, true, true, false],
+                '▜' => [true, true, false, true],
+                '▝' => [false, true, false, false],
+                '▞' => [false, true, true, false],
+                '▟' => [false, true, true, true],
+                _ => [false; 4],
+            };
+            for (i, filled) in quadrants.into_iter().enumerate() {
+                if filled {
+                    let (x, y) = ((i % 2) as f32 / 2.0, (i / 2) as f32 / 2.0);
+                    canvas.fill_fraction(x, y, x + 0.5, y + 0.5, 255);
+                }
+            }
+        }
+    }
+}
+
+fn draw_braille(canvas: &mut Canvas, c: char) {
+    let bits = c as u32 - 0x2800;
+    // dot number to (column, row), dots 7 and 8 were added below the original six
+    const DOTS: [(u32, u32); 8] = [
+        (0, 0),
+        (0, 1),
+        (0, 2),
+        (1, 0),
+        (1, 1),
+        (1, 2),
+        (0, 3),
+        (1, 3),
+    ];
+    let (sub_w, sub_h) = (canvas.w as f32 / 2.0, canvas.h as f32 / 4.0);
+    let dot = (sub_w.min(sub_h) * 0.6).round().max(1.0) as i32;
+
+    for (bit, (col, row)) in DOTS.iter().enumerate() {
+        if bits & (1 << bit) != 0 {
+            let x = ((*col as f32 + 0.5) * sub_w).round() as i32 - dot / 2;
+            let y = ((*row as f32 + 0.5) * sub_h).round() as i32 - dot / 2;
+            canvas.fill(x, y, x + dot, y + dot, 255);
+        }
+    }
+}
+
+/// Draws one geometric character at the given cell size.
+pub(crate) fn draw_geometric_char(c: char, size: UVec2) -> Vec<u8> {
+    let mut canvas = Canvas::new(size);
+    let widths = line_widths(size);
+
+    match c {
+        '╭' => draw_arc(&mut canvas, 1, 1, widths.0),
+        '╮' => draw_arc(&mut canvas, -1, 1, widths.0),
+        '╯' => draw_arc(&mut canvas, -1, -1, widths.0),
+        '╰' => draw_arc(&mut canvas, 1, -1, widths.0),
+        '╱' => draw_diagonal(&mut canvas, true, widths.0),
+        '╲' => draw_diagonal(&mut canvas, false, widths.0),
+        '╳' => {
+            draw_diagonal(&mut canvas, true, widths.0);
+            draw_diagonal(&mut canvas, false, widths.0);
+        }
+        '\u{2500}'..='\u{257F}' => {
+            let (arms, dashes) = BOX_ARMS[(c as u32 - 0x2500) as usize];
+            draw_arms(&mut canvas, arms, dashes, widths);
+        }
+        '\u{2580}'..='\u{259F}' => draw_block(&mut canvas, c),
+        '\u{2800}'..='\u{28FF}' => draw_braille(&mut canvas, c),
+        _ => {}
+    }
+
+    canvas.pixels
+}
+
+/// Draws every geometric character at the given cell size, in physical pixels.
+pub(crate) fn geometric_sheet(size: UVec2) -> GlyphSheet {
+    let mut sheet = GlyphSheet::new(size);
+    for c in ('\u{2500}'..='\u{259F}').chain('\u{2800}'..='\u{28FF}') {
+        sheet.push(&[c], draw_geometric_char(c, size));
+    }
+    sheet
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Coverage of a glyph drawn at the given size, looked up by pixel.
+    struct Drawn {
+        size: UVec2,
+        pixels: Vec<u8>,
+    }
+
+    impl Drawn {
+        fn new(c: char, size: UVec2) -> Self {
+            Drawn {
+                size,
+                pixels: draw_geometric_char(c, size),
+            }
+        }
+
+        fn at(&self, x: u32, y: u32) -> u8 {
+            self.pixels[(y * self.size.x + x) as usize]
+        }
+
+        /// Whether the pixel at the centre of a fraction of the cell is covered.
+        fn covered(&self, fx: f32, fy: f32) -> bool {
+            let x = (fx * self.size.x as f32) as u32;
+            let y = (fy * self.size.y as f32) as u32;
+            self.at(x, y) == 255
+        }
+    }
+
+    const SIZES: [UVec2; 3] = [UVec2::new(8, 16), UVec2::new(9, 17), UVec2::new(10, 21)];
+
+    #[test]
+    fn arms_reach_both_cell_edges_on_the_centre_line() {
+        for size in SIZES {
+            let (cx, cy) = (size.x / 2, size.y / 2);
+            for c in ['─', '┼'] {
+                let drawn = Drawn::new(c, size);
+                assert!((0..size.x).all(|x| drawn.at(x, cy) == 255), "{c} at {size}");
+            }
+            for c in ['│', '┼'] {
+                let drawn = Drawn::new(c, size);
+                assert!((0..size.y).all(|y| drawn.at(cx, y) == 255), "{c} at {size}");
+            }
+            // and nothing but the lines is drawn
+            let cross = Drawn::new('┼', size);
+            assert_eq!(cross.at(0, 0), 0, "┼ at {size}");
+            assert_eq!(cross.at(size.x - 1, size.y - 1), 0, "┼ at {size}");
+        }
+    }
+
+    #[test]
+    fn eighth_blocks_cover_their_share_of_the_cell() {
+        let size = UVec2::new(8, 16);
+        for n in 1..=8 {
+            // lower blocks grow up from the bottom, left blocks grow right from the left edge
+            let lower = Drawn::new(char::from_u32(0x2580 + n).unwrap(), size);
+            let left = Drawn::new(char::from_u32(0x2590 - n).unwrap(), size);
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    assert_eq!(lower.at(x, y) == 255, y >= 16 - 2 * n, "lower {n}/8");
+                    assert_eq!(left.at(x, y) == 255, x < n, "left {n}/8");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn quadrants_match_their_names() {
+        // upper left, upper right, lower left, lower right
+        for (c, filled) in [
+            ('▀', "1100"),
+            ('▄', "0011"),
+            ('▌', "1010"),
+            ('▐', "0101"),
+            ('▖', "0010"),
+            ('▗', "0001"),
+            ('▘', "1000"),
+            ('▙', "1011"),
+            ('▚', "1001"),
+            ('▛', "1110"),
+            ('▜', "1101"),
+            ('▝', "0100"),
+            ('▞', "0110"),
+            ('▟', "0111"),
+        ] {
+            for size in SIZES {
+                let drawn = Drawn::new(c, size);
+                for (i, filled) in filled.chars().enumerate() {
+                    let (fx, fy) = (0.25 + (i % 2) as f32 / 2.0, 0.25 + (i / 2) as f32 / 2.0);
+                    assert_eq!(
+                        drawn.covered(fx, fy),
+                        filled == '1',
+                        "{c} quadrant {i} at {size}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn braille_dots_match_the_code_point_bits() {
+        // dots 1 to 3 and 7 down the left column, 4 to 6 and 8 down the right
+        let dots = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 1),
+            (1, 2),
+            (0, 3),
+            (1, 3),
+        ];
+        for size in SIZES {
+            for c in '\u{2800}'..='\u{28FF}' {
+                let drawn = Drawn::new(c, size);
+                for (bit, (col, row)) in dots.into_iter().enumerate() {
+                    let set = (c as u32 - 0x2800) & (1 << bit) != 0;
+                    let (fx, fy) = ((col as f32 + 0.5) / 2.0, (row as f32 + 0.5) / 4.0);
+                    assert_eq!(drawn.covered(fx, fy), set, "{c} dot {} at {size}", bit + 1);
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use bevy::prelude::{default, Color as BevyColor, Component, Handle, TextStyle};
+use bevy::prelude::{
+    default, Assets, Color as BevyColor, Component, Handle, Image, TextStyle, TextureAtlasLayout,
+};
 
 use ratatui::{
     buffer::Cell,
@@ -6,13 +8,16 @@ use ratatui::{
     terminal::Terminal,
 };
 
-use crate::BevyBackend;
+use crate::{box_drawing::is_geometric_char, BevyBackend, BitmapFont};
 
+/// A terminal, it can be spawned and despawned at any time. The plugin turns its entity into a UI
+/// node with the cells as children, which are despawned along with it.
 #[derive(Component, Debug, Clone)]
 pub struct TerminalComponent {
     pub ratatui_terminal: Terminal<BevyBackend>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FontStyle {
     Normal,
     Bold,
@@ -21,6 +26,58 @@ pub enum FontStyle {
 }
 
 impl TerminalComponent {
+    /// Handle of the bitmap font used for a style, if that style was given a bitmap font path.
+    pub fn get_bitmap_font(&self, font_style: FontStyle) -> Option<&Handle<BitmapFont>> {
+        let termy_backend = self.ratatui_terminal.backend();
+        match font_style {
+            FontStyle::Normal => termy_backend.normal_bitmap.as_ref(),
+            FontStyle::Bold => termy_backend.bold_bitmap.as_ref(),
+            FontStyle::Italic => termy_backend.italic_bitmap.as_ref(),
+            FontStyle::ItalicBold => termy_backend.italicbold_bitmap.as_ref(),
+        }
+    }
+
+    /// The atlas tile a cell is drawn with instead of font text, if there is one. Tileset
+    /// mappings come first, then procedural box drawing, then bitmap fonts.
+    pub fn atlas_glyph(
+        &self,
+        font_style: FontStyle,
+        cell: &Cell,
+        bitmap_fonts: &Assets<BitmapFont>,
+    ) -> Option<AtlasGlyph> {
+        let termy_backend = self.ratatui_terminal.backend();
+        let symbol = cell.symbol();
+
+        if let Some(tileset) = &termy_backend.tileset {
+            if let Some(index) = tileset.tile(symbol, cell.fg) {
+                return Some(AtlasGlyph {
+                    image: tileset.image.clone(),
+                    layout: tileset.layout.clone(),
+                    index,
+                    tinted: tileset.tinted,
+                });
+            }
+        }
+
+        let geometric = termy_backend
+            .geometric_font
+            .as_ref()
+            .filter(|_| termy_backend.geometric_glyphs)
+            .filter(|_| symbol.chars().next().is_some_and(is_geometric_char))
+            .and_then(|handle| bitmap_fonts.get(handle));
+
+        let bitmap = match geometric {
+            Some(geometric) => geometric,
+            None => bitmap_fonts.get(self.get_bitmap_font(font_style)?)?,
+        };
+        Some(AtlasGlyph {
+            image: bitmap.image.clone(),
+            layout: bitmap.layout.clone(),
+            index: bitmap.glyph(symbol)?,
+            tinted: true,
+        })
+    }
+
     pub fn get_text_style(&self, color: BevyColor, font_style: FontStyle) -> TextStyle {
         let rat_term = &self.ratatui_terminal;
         let termy_backend = rat_term.backend();
@@ -35,40 +92,46 @@ impl TerminalComponent {
         if boop != Handle::weak_from_u128(101) {
             TextStyle {
                 font: boop,
-                font_size: termy_backend.term_font_size as f32,
-                color: color,
-
-                ..default()
+                font_size: termy_backend.term_font_size as f32 * termy_backend.font_scale,
+                color,
             }
         } else {
             TextStyle {
-                font_size: termy_backend.term_font_size as f32,
-                color: color,
-
+                font_size: termy_backend.term_font_size as f32 * termy_backend.font_scale,
+                color,
                 ..default()
             }
         }
     }
 }
 
+/// A tile of a texture atlas that a cell is drawn with instead of text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtlasGlyph {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    pub index: usize,
+    /// Tinted with the cell foreground, otherwise drawn in the tile's own colours
+    pub tinted: bool,
+}
+
+/// Marks the child of a cell that draws its AtlasGlyph.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlyphSprite;
+
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct SlowBlink {
     pub in_blink: bool,
     pub true_color: BevyColor,
 }
 
-#[derive(Component, Debug, Clone, PartialEq)]
-pub struct Cursor {
-    pub pos: (u16, u16),
-}
-
 #[derive(Component, Debug, Clone, PartialEq)]
 pub struct RapidBlink {
     pub in_blink: bool,
     pub true_color: BevyColor,
 }
 
-#[derive(Component, Debug, Clone)]
+#[derive(Component, Debug, Clone, Default)]
 pub struct CellComponent {
     pub cell: Cell,
 }
@@ -81,7 +144,7 @@ impl CellComponent {
     }
 
     pub fn from_cell(cell: Cell) -> Self {
-        CellComponent { cell: cell }
+        CellComponent { cell }
     }
 
     pub fn fg(&self) -> BevyColor {
@@ -124,6 +187,19 @@ impl CellComponent {
         self.cell.skip
     }
 
+    /// Which of the four terminal fonts the cell is drawn with.
+    pub fn font_style(&self) -> FontStyle {
+        if self.bold() && self.italic() {
+            FontStyle::ItalicBold
+        } else if self.bold() {
+            FontStyle::Bold
+        } else if self.italic() {
+            FontStyle::Italic
+        } else {
+            FontStyle::Normal
+        }
+    }
+
     pub fn proper_symbol(&self) -> String {
         let mut proper_value = self.cell.symbol().to_string();
 
@@ -142,9 +218,7 @@ impl CellComponent {
         let mut proper_bg = self.bg();
 
         if self.reversed() {
-            let col_buf = proper_fg.clone();
-            proper_fg = proper_bg;
-            proper_bg = col_buf;
+            std::mem::swap(&mut proper_fg, &mut proper_bg);
         }
 
         if self.dim() {
@@ -155,7 +229,7 @@ impl CellComponent {
         }
 
         if self.hidden() {
-            proper_fg = proper_bg.clone();
+            proper_fg = proper_bg;
         }
 
         (proper_fg, proper_bg)
@@ -194,11 +268,11 @@ impl FromRatColor<RatColor> for BevyColor {
     }
 }
 
-trait FromAnsi<u8> {
+trait FromAnsi {
     fn from_ansi(beep: u8) -> BevyColor;
 }
 
-impl FromAnsi<u8> for BevyColor {
+impl FromAnsi for BevyColor {
     fn from_ansi(beep: u8) -> BevyColor {
         BevyColor::rgb_u8(beep, beep, beep)
     }
@@ -0,0 +1,195 @@
+//! A system parameter for getting at terminals without querying and unwrapping by hand. The
+//! default terminal is the one marked [`DefaultTerminal`], or the only terminal there is, and
+//! other terminals can be looked up by their [`TerminalName`].
+
+use std::{
+    error::Error,
+    fmt, io,
+    ops::{Deref, DerefMut},
+};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use ratatui::{layout::Rect, Frame};
+
+use crate::components::TerminalComponent;
+
+/// Marks the terminal [`RatatuiContext`] uses when there is more than one.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct DefaultTerminal;
+
+/// A name to look a terminal up by with [`RatatuiContext::named`].
+#[derive(Component, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TerminalName(pub String);
+
+impl TerminalName {
+    pub fn new(name: impl Into<String>) -> Self {
+        TerminalName(name.into())
+    }
+}
+
+/// Errors from getting at or drawing to a terminal through a [`RatatuiContext`].
+#[derive(Debug)]
+pub enum ContextError {
+    /// There is no terminal, none was spawned or its spawn command hasn't been applied yet
+    NoTerminal,
+    /// No terminal has this name
+    NotFound(String),
+    /// There are several terminals and none of them is the [`DefaultTerminal`]
+    NoDefault,
+    Io(io::Error),
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextError::NoTerminal => write!(f, "there is no terminal yet"),
+            ContextError::NotFound(name) => write!(f, "there is no terminal named {:?}", name),
+            ContextError::NoDefault => {
+                write!(f, "there are several terminals and none is the default")
+            }
+            ContextError::Io(e) => write!(f, "terminal backend error: {}", e),
+        }
+    }
+}
+
+impl Error for ContextError {}
+
+impl From<io::Error> for ContextError {
+    fn from(e: io::Error) -> Self {
+        ContextError::Io(e)
+    }
+}
+
+/// One terminal found through a [`RatatuiContext`], derefs to its [`TerminalComponent`].
+pub struct TerminalHandle<'a> {
+    termy: Mut<'a, TerminalComponent>,
+}
+
+impl TerminalHandle<'_> {
+    /// Draws a frame, like `Terminal::draw`.
+    pub fn draw(&mut self, f: impl FnOnce(&mut Frame)) -> Result<(), ContextError> {
+        self.termy.ratatui_terminal.draw(f)?;
+        Ok(())
+    }
+
+    /// The size of the grid.
+    pub fn size(&self) -> Result<Rect, ContextError> {
+        Ok(self.termy.ratatui_terminal.size()?)
+    }
+
+    pub fn show_cursor(&mut self) -> Result<(), ContextError> {
+        Ok(self.termy.ratatui_terminal.show_cursor()?)
+    }
+
+    pub fn hide_cursor(&mut self) -> Result<(), ContextError> {
+        Ok(self.termy.ratatui_terminal.hide_cursor()?)
+    }
+
+    pub fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), ContextError> {
+        Ok(self.termy.ratatui_terminal.set_cursor(x, y)?)
+    }
+}
+
+impl Deref for TerminalHandle<'_> {
+    type Target = TerminalComponent;
+
+    fn deref(&self) -> &TerminalComponent {
+        &self.termy
+    }
+}
+
+impl DerefMut for TerminalHandle<'_> {
+    fn deref_mut(&mut self) -> &mut TerminalComponent {
+        &mut self.termy
+    }
+}
+
+/// Access to the terminals for drawing systems, see the module docs.
+#[derive(SystemParam)]
+pub struct RatatuiContext<'w, 's> {
+    terminals: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static mut TerminalComponent,
+            Option<&'static TerminalName>,
+            Has<DefaultTerminal>,
+        ),
+    >,
+}
+
+impl RatatuiContext<'_, '_> {
+    fn default_entity(&self) -> Result<Entity, ContextError> {
+        let mut all = self.terminals.iter();
+        let Some((first, ..)) = all.next() else {
+            return Err(ContextError::NoTerminal);
+        };
+        if all.next().is_none() {
+            return Ok(first);
+        }
+        self.terminals
+            .iter()
+            .find(|(.., default)| *default)
+            .map(|(entity, ..)| entity)
+            .ok_or(ContextError::NoDefault)
+    }
+
+    fn named_entity(&self, name: &str) -> Result<Entity, ContextError> {
+        self.terminals
+            .iter()
+            .find(|(_, _, terminal_name, _)| terminal_name.is_some_and(|n| n.0 == name))
+            .map(|(entity, ..)| entity)
+            .ok_or_else(|| ContextError::NotFound(name.to_string()))
+    }
+
+    fn handle(&mut self, entity: Entity) -> Result<TerminalHandle<'_>, ContextError> {
+        let (_, termy, ..) = self
+            .terminals
+            .get_mut(entity)
+            .map_err(|_| ContextError::NoTerminal)?;
+        Ok(TerminalHandle { termy })
+    }
+
+    /// The default terminal.
+    pub fn terminal(&mut self) -> Result<TerminalHandle<'_>, ContextError> {
+        let entity = self.default_entity()?;
+        self.handle(entity)
+    }
+
+    /// The terminal with this [`TerminalName`].
+    pub fn named(&mut self, name: &str) -> Result<TerminalHandle<'_>, ContextError> {
+        let entity = self.named_entity(name)?;
+        self.handle(entity)
+    }
+
+    /// The terminal on this entity.
+    pub fn get(&mut self, entity: Entity) -> Result<TerminalHandle<'_>, ContextError> {
+        self.handle(entity)
+    }
+
+    /// Draws a frame to the default terminal.
+    pub fn draw(&mut self, f: impl FnOnce(&mut Frame)) -> Result<(), ContextError> {
+        self.terminal()?.draw(f)
+    }
+
+    /// The grid size of the default terminal.
+    pub fn size(&mut self) -> Result<Rect, ContextError> {
+        self.terminal()?.size()
+    }
+
+    /// Shows the cursor of the default terminal.
+    pub fn show_cursor(&mut self) -> Result<(), ContextError> {
+        self.terminal()?.show_cursor()
+    }
+
+    /// Hides the cursor of the default terminal.
+    pub fn hide_cursor(&mut self) -> Result<(), ContextError> {
+        self.terminal()?.hide_cursor()
+    }
+
+    /// Moves the cursor of the default terminal.
+    pub fn set_cursor(&mut self, x: u16, y: u16) -> Result<(), ContextError> {
+        self.terminal()?.set_cursor(x, y)
+    }
+}
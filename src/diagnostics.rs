@@ -0,0 +1,102 @@
+//! What drawing terminals costs, as Bevy diagnostics. Add [`TerminalDiagnosticsPlugin`] and view
+//! them with `LogDiagnosticsPlugin` or read them from the `DiagnosticsStore` for an overlay.
+
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic},
+    prelude::*,
+    utils::{Duration, HashMap, Instant},
+};
+
+/// Adds the terminal diagnostics, see the module docs.
+#[derive(Default)]
+pub struct TerminalDiagnosticsPlugin;
+
+impl Plugin for TerminalDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SyncStats>()
+            .register_diagnostic(Diagnostic::new(Self::CELLS_CHANGED))
+            .register_diagnostic(Diagnostic::new(Self::ENTITIES_TOUCHED))
+            .register_diagnostic(Diagnostic::new(Self::VCUPDATE_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::COMP_TIME).with_suffix("ms"))
+            .register_diagnostic(Diagnostic::new(Self::REBUILD_TIME).with_suffix("ms"))
+            .add_systems(Last, diagnostic_system);
+    }
+}
+
+impl TerminalDiagnosticsPlugin {
+    /// Cells whose content changed this frame
+    pub const CELLS_CHANGED: DiagnosticPath = DiagnosticPath::const_new("ratatui/cells_changed");
+    /// Cell and row entities whose text, background or quads were updated this frame
+    pub const ENTITIES_TOUCHED: DiagnosticPath =
+        DiagnosticPath::const_new("ratatui/entities_touched");
+    /// Time spent copying drawn cells into their entities
+    pub const VCUPDATE_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("ratatui/update_ents_from_vcupdate");
+    /// Time spent turning changed cells, or rows in `RenderMode::Rows`, into text and backgrounds
+    pub const COMP_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("ratatui/update_ents_from_comp");
+    /// Time from the frame the cells of a terminal are despawned for a rebuild to the start of the
+    /// first frame after the new cells were spawned, so the frames in between and the layout of
+    /// the new cells count too. Measured once per rebuild, the longest one when several
+    /// terminals are rebuilt together
+    pub const REBUILD_TIME: DiagnosticPath = DiagnosticPath::const_new("ratatui/resize_rebuild");
+}
+
+/// Counted by the sync systems while the diagnostics plugin is added, and reset every frame.
+#[derive(Resource, Default)]
+pub(crate) struct SyncStats {
+    pub(crate) cells_changed: usize,
+    pub(crate) entities_touched: usize,
+    pub(crate) vcupdate_time: Duration,
+    pub(crate) comp_time: Duration,
+    /// When the cells of the terminals being rebuilt were despawned
+    pub(crate) rebuild_started: HashMap<Entity, Instant>,
+    /// Terminals whose cells were spawned again, and whether the frame that spawned them is over
+    pub(crate) rebuilt: Vec<(Entity, bool)>,
+    pub(crate) rebuild_time: Option<Duration>,
+}
+
+/// Times the rebuilds whose new cells were spawned on an earlier frame, at the start of this one.
+pub(crate) fn finish_rebuild_times(mut stats: ResMut<SyncStats>) {
+    let stats = &mut *stats;
+    let now = Instant::now();
+    stats.rebuilt.retain(|(terminal, frame_over)| {
+        if !frame_over {
+            return true;
+        }
+        if let Some(started) = stats.rebuild_started.remove(terminal) {
+            let time = now - started;
+            stats.rebuild_time = Some(stats.rebuild_time.map_or(time, |t| t.max(time)));
+        }
+        false
+    });
+}
+
+fn diagnostic_system(mut diagnostics: Diagnostics, mut stats: ResMut<SyncStats>) {
+    let cells_changed = std::mem::take(&mut stats.cells_changed);
+    let entities_touched = std::mem::take(&mut stats.entities_touched);
+    let vcupdate_time = std::mem::take(&mut stats.vcupdate_time);
+    let comp_time = std::mem::take(&mut stats.comp_time);
+    // the cells spawned this frame are timed at the start of the next one
+    for (_, frame_over) in stats.rebuilt.iter_mut() {
+        *frame_over = true;
+    }
+
+    diagnostics.add_measurement(&TerminalDiagnosticsPlugin::CELLS_CHANGED, || {
+        cells_changed as f64
+    });
+    diagnostics.add_measurement(&TerminalDiagnosticsPlugin::ENTITIES_TOUCHED, || {
+        entities_touched as f64
+    });
+    diagnostics.add_measurement(&TerminalDiagnosticsPlugin::VCUPDATE_TIME, || {
+        vcupdate_time.as_secs_f64() * 1000.0
+    });
+    diagnostics.add_measurement(&TerminalDiagnosticsPlugin::COMP_TIME, || {
+        comp_time.as_secs_f64() * 1000.0
+    });
+    if let Some(rebuild_time) = stats.rebuild_time.take() {
+        diagnostics.add_measurement(&TerminalDiagnosticsPlugin::REBUILD_TIME, || {
+            rebuild_time.as_secs_f64() * 1000.0
+        });
+    }
+}
@@ -0,0 +1,64 @@
+//! Problems the plugin's systems run into. They are sent as [`TerminalError`] events and logged
+//! instead of panicking, and the system tries again on a later frame.
+
+use std::{error::Error, fmt};
+
+use bevy::prelude::*;
+
+/// What went wrong, see [`TerminalError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TerminalErrorKind {
+    /// There is no primary window to fit to the terminal, it may have been closed
+    NoWindow,
+    /// An entity the terminal owns was despawned by someone else
+    MissingEntity(Entity),
+    /// A terminal with widget children is already drawn by a threaded app, cast player, pty or
+    /// ratatui app, so its widgets are left out
+    DrivenTerminal(Entity),
+}
+
+/// Sent when one of the plugin's systems can't do its job this frame.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TerminalError {
+    /// The system that ran into it
+    pub system: &'static str,
+    pub kind: TerminalErrorKind,
+}
+
+impl TerminalError {
+    pub fn new(system: &'static str, kind: TerminalErrorKind) -> Self {
+        TerminalError { system, kind }
+    }
+}
+
+impl fmt::Display for TerminalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.system)?;
+        match self.kind {
+            TerminalErrorKind::NoWindow => write!(f, "there is no primary window"),
+            TerminalErrorKind::MissingEntity(entity) => {
+                write!(f, "entity {:?} of the terminal was despawned", entity)
+            }
+            TerminalErrorKind::DrivenTerminal(entity) => write!(
+                f,
+                "terminal {:?} is drawn by something else, its widgets are not drawn",
+                entity
+            ),
+        }
+    }
+}
+
+impl Error for TerminalError {}
+
+/// Logs every error once, until a different one comes along.
+pub(crate) fn log_terminal_errors(
+    mut errors: EventReader<TerminalError>,
+    mut last: Local<Option<TerminalError>>,
+) {
+    for error in errors.read() {
+        if *last != Some(*error) {
+            warn!("{}", error);
+            *last = Some(*error);
+        }
+    }
+}
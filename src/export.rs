@@ -0,0 +1,219 @@
+//! Dumps a ratatui buffer as plain text, ANSI escaped text or a standalone HTML page, for bug
+//! reports, logs and golden tests. Every line of the area ends with a newline and the cells
+//! covered by wide characters are skipped, so the output lines up the same way the screen does.
+
+use std::fmt::Write;
+
+use bevy::prelude::Color as BevyColor;
+use ratatui::{
+    buffer::{Buffer, Cell},
+    layout::Rect,
+    style::{Color as RatColor, Modifier},
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::components::CellComponent;
+
+/// Calls `f` with every visible cell of a row, skipping the cells under wide characters.
+fn visible_cells<'a>(buffer: &'a Buffer, area: Rect, y: u16, mut f: impl FnMut(u16, &'a Cell)) {
+    let mut covered = 0;
+    for x in area.left()..area.right() {
+        let cell = buffer.get(x, y);
+        if covered > 0 {
+            covered -= 1;
+            continue;
+        }
+        covered = cell.symbol().width().saturating_sub(1);
+        f(x, cell);
+    }
+}
+
+/// The area clipped to the buffer, so out of range rects don't panic.
+fn clipped(buffer: &Buffer, area: Rect) -> Rect {
+    buffer.area.intersection(area)
+}
+
+/// Text of the area without any styling, trailing spaces of each line trimmed.
+pub fn export_text(buffer: &Buffer, area: Rect) -> String {
+    let area = clipped(buffer, area);
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut line = String::new();
+        visible_cells(buffer, area, y, |_, cell| line.push_str(cell.symbol()));
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// Text of the area with SGR escape codes, using the colours of the terminal it is printed in.
+pub fn export_ansi(buffer: &Buffer, area: Rect) -> String {
+    let area = clipped(buffer, area);
+    let mut out = String::new();
+    for y in area.top()..area.bottom() {
+        let mut last: Option<(RatColor, RatColor, Modifier)> = None;
+        visible_cells(buffer, area, y, |_, cell| {
+            let style = (cell.fg, cell.bg, cell.modifier);
+            if last != Some(style) {
+                out.push_str(&sgr(cell));
+                last = Some(style);
+            }
+            out.push_str(cell.symbol());
+        });
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// Every cell of the buffer with its position, leaving out the cells under wide characters like
+/// ratatui's own diff does.
+pub(crate) fn screen_cells(buffer: &Buffer) -> Vec<(u16, u16, Cell)> {
+    let mut cells = Vec::new();
+    for y in buffer.area.top()..buffer.area.bottom() {
+        visible_cells(buffer, buffer.area, y, |x, cell| {
+            cells.push((x, y, cell.clone()))
+        });
+    }
+    cells
+}
+
+/// A batch of drawn cells as ANSI output, moving the cursor only where the cells aren't
+/// contiguous and changing the style only where it changes.
+pub(crate) fn ansi_batch(cells: &[(u16, u16, Cell)]) -> String {
+    let mut data = String::new();
+    let mut next_pos = None;
+    let mut last_sgr = String::new();
+    for (x, y, cell) in cells {
+        if next_pos != Some((*x, *y)) {
+            let _ = write!(data, "\x1b[{};{}H", y + 1, x + 1);
+        }
+        let cell_sgr = sgr(cell);
+        if cell_sgr != last_sgr {
+            data.push_str(&cell_sgr);
+            last_sgr = cell_sgr;
+        }
+        data.push_str(cell.symbol());
+        next_pos = Some((x + cell.symbol().width().max(1) as u16, *y));
+    }
+    data.push_str("\x1b[0m");
+    data
+}
+
+/// The full SGR sequence for a cell, starting from a reset so styles never leak between runs.
+pub(crate) fn sgr(cell: &Cell) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::SLOW_BLINK, "5"),
+        (Modifier::RAPID_BLINK, "6"),
+        (Modifier::REVERSED, "7"),
+        (Modifier::HIDDEN, "8"),
+        (Modifier::CROSSED_OUT, "9"),
+    ] {
+        if cell.modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    codes.push(ansi_color(cell.fg, true));
+    codes.push(ansi_color(cell.bg, false));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+fn ansi_color(color: RatColor, fg: bool) -> String {
+    let base = if fg { 30 } else { 40 };
+    let named = |n: u8| (base + n).to_string();
+    let bright = |n: u8| (base + 60 + n).to_string();
+    match color {
+        RatColor::Reset => (base + 9).to_string(),
+        RatColor::Black => named(0),
+        RatColor::Red => named(1),
+        RatColor::Green => named(2),
+        RatColor::Yellow => named(3),
+        RatColor::Blue => named(4),
+        RatColor::Magenta => named(5),
+        RatColor::Cyan => named(6),
+        RatColor::Gray => named(7),
+        RatColor::DarkGray => bright(0),
+        RatColor::LightRed => bright(1),
+        RatColor::LightGreen => bright(2),
+        RatColor::LightYellow => bright(3),
+        RatColor::LightBlue => bright(4),
+        RatColor::LightMagenta => bright(5),
+        RatColor::LightCyan => bright(6),
+        RatColor::White => bright(7),
+        RatColor::Indexed(i) => format!("{};5;{}", base + 8, i),
+        RatColor::Rgb(r, g, b) => format!("{};2;{};{};{}", base + 8, r, g, b),
+    }
+}
+
+/// A standalone HTML page showing the area in the colours the Bevy terminal draws it with.
+pub fn export_html(buffer: &Buffer, area: Rect) -> String {
+    let area = clipped(buffer, area);
+    let mut out = String::from(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<style>\n\
+         pre { font-family: monospace; line-height: 1.2; }\n\
+         </style>\n</head>\n<body>\n<pre>",
+    );
+    for y in area.top()..area.bottom() {
+        let mut run = String::new();
+        let mut run_style = String::new();
+        visible_cells(buffer, area, y, |_, cell| {
+            let style = css(cell);
+            if style != run_style && !run.is_empty() {
+                push_span(&mut out, &run_style, &run);
+                run.clear();
+            }
+            run_style = style;
+            escape_html(&mut run, cell.symbol());
+        });
+        if !run.is_empty() {
+            push_span(&mut out, &run_style, &run);
+        }
+        out.push('\n');
+    }
+    out.push_str("</pre>\n</body>\n</html>\n");
+    out
+}
+
+fn push_span(out: &mut String, style: &str, text: &str) {
+    let _ = write!(out, "<span style=\"{}\">{}</span>", style, text);
+}
+
+fn css(cell: &Cell) -> String {
+    let cellii = CellComponent::from_cell(cell.clone());
+    let (fg, bg) = cellii.proper_fg_bg();
+    let mut style = format!("color:{};background-color:{}", hex(fg), hex(bg));
+    if cellii.bold() {
+        style.push_str(";font-weight:bold");
+    }
+    if cellii.italic() {
+        style.push_str(";font-style:italic");
+    }
+    match (cellii.underlined(), cellii.crossed_out()) {
+        (true, true) => style.push_str(";text-decoration:underline line-through"),
+        (true, false) => style.push_str(";text-decoration:underline"),
+        (false, true) => style.push_str(";text-decoration:line-through"),
+        (false, false) => {}
+    }
+    style
+}
+
+fn hex(color: BevyColor) -> String {
+    let [r, g, b, _] = color.as_rgba_u8();
+    format!("#{:02x}{:02x}{:02x}", r, g, b)
+}
+
+fn escape_html(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! A ratatui widget that draws a Bevy [`Image`] out of cells, two pixels per cell using the upper
+//! half block with the top pixel as foreground and the bottom pixel as background. It only touches
+//! the buffer, so it works with any backend including headless ones.
+
+use bevy::{prelude::Image, render::render_resource::TextureFormat};
+use ratatui::{buffer::Buffer, layout::Rect, style::Color as RatColor, widgets::Widget};
+
+/// How the image is scaled into the area it is rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFit {
+    /// Keep the aspect ratio and show the whole image, leaving the rest of the area untouched
+    #[default]
+    Fit,
+    /// Keep the aspect ratio and cover the whole area, cropping what sticks out
+    Fill,
+    /// Scale each axis on its own to cover the area exactly
+    Stretch,
+}
+
+/// Draws an image with half block characters, see the module docs.
+#[derive(Debug, Clone, Copy)]
+pub struct HalfBlockImage<'a> {
+    image: &'a Image,
+    fit: ImageFit,
+    dither: bool,
+}
+
+const BAYER_4X4: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Channel values of the 6x6x6 colour cube of 256 colour terminals.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl<'a> HalfBlockImage<'a> {
+    pub fn new(image: &'a Image) -> Self {
+        HalfBlockImage {
+            image,
+            fit: ImageFit::default(),
+            dither: false,
+        }
+    }
+
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    /// Ordered dithering down to the 256 colour cube, for output that ends up on real terminals.
+    pub fn dither(mut self, value: bool) -> Self {
+        self.dither = value;
+        self
+    }
+
+    /// Quantizes a colour to the colour cube, nudged by the Bayer threshold of its position.
+    fn dithered(rgba: [u8; 4], x: u16, y: u16) -> [u8; 4] {
+        let threshold = BAYER_4X4[y as usize % 4][x as usize % 4] as f32 / 16.0 - 0.5;
+        let mut out = rgba;
+        for channel in out.iter_mut().take(3) {
+            // the cube levels are 40 apart above 95, use that as the dither step
+            let nudged = (*channel as f32 + threshold * 40.0).clamp(0.0, 255.0);
+            *channel = CUBE_LEVELS
+                .into_iter()
+                .min_by_key(|level| (*level as f32 - nudged).abs() as u32)
+                .unwrap_or(0);
+        }
+        out
+    }
+}
+
+impl Widget for HalfBlockImage<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let size = self.image.texture_descriptor.size;
+        let (iw, ih) = (size.width as f32, size.height as f32);
+        if area.is_empty() || iw == 0.0 || ih == 0.0 {
+            return;
+        }
+
+        // every cell holds two pixels stacked on top of each other
+        let (tw, th) = (area.width as f32, area.height as f32 * 2.0);
+        let (sx, sy) = match self.fit {
+            ImageFit::Stretch => (tw / iw, th / ih),
+            ImageFit::Fit => {
+                let scale = (tw / iw).min(th / ih);
+                (scale, scale)
+            }
+            ImageFit::Fill => {
+                let scale = (tw / iw).max(th / ih);
+                (scale, scale)
+            }
+        };
+        let offset = ((tw - iw * sx) / 2.0, (th - ih * sy) / 2.0);
+
+        let sample = |px: u16, py: u16| -> Option<[u8; 4]> {
+            let u = (px as f32 + 0.5 - offset.0) / sx;
+            let v = (py as f32 + 0.5 - offset.1) / sy;
+            if u < 0.0 || v < 0.0 || u >= iw || v >= ih {
+                return None;
+            }
+            let rgba = image_pixel(self.image, u as u32, v as u32)?;
+            if rgba[3] < 128 {
+                return None;
+            }
+            Some(if self.dither {
+                Self::dithered(rgba, px, py)
+            } else {
+                rgba
+            })
+        };
+
+        // the layout follows the whole area, only the part inside the buffer is drawn
+        let visible = area.intersection(buf.area);
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                let (px, py) = (x - area.x, (y - area.y) * 2);
+                let top = sample(px, py);
+                let bottom = sample(px, py + 1);
+                let cell = buf.get_mut(x, y);
+                // transparent halves keep the background already in the cell
+                match (top, bottom) {
+                    (Some(top), Some(bottom)) => {
+                        cell.set_symbol("▀").set_fg(rgb(top)).set_bg(rgb(bottom));
+                    }
+                    (Some(top), None) => {
+                        cell.set_symbol("▀").set_fg(rgb(top));
+                    }
+                    (None, Some(bottom)) => {
+                        cell.set_symbol("▄").set_fg(rgb(bottom));
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+    }
+}
+
+/// RGBA of a pixel of an image kept on the CPU, None for formats that can't be read.
+pub(crate) fn image_pixel(image: &Image, x: u32, y: u32) -> Option<[u8; 4]> {
+    let size = image.texture_descriptor.size;
+    let format = image.texture_descriptor.format;
+    if x >= size.width || y >= size.height {
+        return None;
+    }
+    let bytes = format.block_copy_size(None)? as usize;
+    let offset = (y as usize * size.width as usize + x as usize) * bytes;
+    let px = image.data.get(offset..offset + bytes)?;
+
+    match format {
+        TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => {
+            Some([px[0], px[1], px[2], px[3]])
+        }
+        TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => {
+            Some([px[2], px[1], px[0], px[3]])
+        }
+        TextureFormat::R8Unorm => Some([px[0], px[0], px[0], 255]),
+        TextureFormat::Rg8Unorm => Some([px[0], px[0], px[0], px[1]]),
+        _ => None,
+    }
+}
+
+fn rgb(rgba: [u8; 4]) -> RatColor {
+    RatColor::Rgb(rgba[0], rgba[1], rgba[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension},
+    };
+    use ratatui::style::Color;
+
+    use super::*;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+
+    fn image(width: u32, height: u32, pixels: &[[u8; 4]]) -> Image {
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixels.concat(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    fn render(widget: HalfBlockImage, area: Rect, buffer_area: Rect) -> Buffer {
+        let mut buf = Buffer::empty(buffer_area);
+        widget.render(area, &mut buf);
+        buf
+    }
+
+    /// (symbol, fg, bg) of every cell, row by row.
+    fn cells(buf: &Buffer) -> Vec<(&str, Color, Color)> {
+        buf.content
+            .iter()
+            .map(|c| (c.symbol(), c.fg, c.bg))
+            .collect()
+    }
+
+    fn red() -> Color {
+        Color::Rgb(255, 0, 0)
+    }
+
+    fn blue() -> Color {
+        Color::Rgb(0, 0, 255)
+    }
+
+    #[test]
+    fn fit_centers_the_image_and_leaves_the_rest() {
+        // a red over blue square in a 3x1 area is one cell wide in the middle
+        let image = image(1, 2, &[RED, BLUE]);
+        let area = Rect::new(0, 0, 3, 1);
+        let buf = render(HalfBlockImage::new(&image), area, area);
+        assert_eq!(
+            cells(&buf),
+            [
+                (" ", Color::Reset, Color::Reset),
+                ("▀", red(), blue()),
+                (" ", Color::Reset, Color::Reset),
+            ]
+        );
+    }
+
+    #[test]
+    fn fill_covers_the_area_and_crops() {
+        // a 2x2 image scaled to cover 1x1 cell only shows its middle column
+        let image = image(2, 2, &[RED, BLUE, BLUE, RED]);
+        let area = Rect::new(0, 0, 1, 1);
+        let buf = render(HalfBlockImage::new(&image).fit(ImageFit::Fill), area, area);
+        assert_eq!(cells(&buf), [("▀", blue(), red())]);
+    }
+
+    #[test]
+    fn stretch_scales_each_axis() {
+        let image = image(1, 2, &[RED, BLUE]);
+        let area = Rect::new(0, 0, 3, 2);
+        let buf = render(
+            HalfBlockImage::new(&image).fit(ImageFit::Stretch),
+            area,
+            area,
+        );
+        assert_eq!(
+            cells(&buf),
+            [
+                ("▀", red(), red()),
+                ("▀", red(), red()),
+                ("▀", red(), red()),
+                ("▀", blue(), blue()),
+                ("▀", blue(), blue()),
+                ("▀", blue(), blue()),
+            ]
+        );
+    }
+
+    #[test]
+    fn transparent_halves_keep_the_cell_background() {
+        let image = image(3, 2, &[RED, CLEAR, CLEAR, BLUE, BLUE, CLEAR]);
+        let area = Rect::new(0, 0, 3, 1);
+        let mut buf = Buffer::empty(area);
+        buf.set_style(area, ratatui::style::Style::default().bg(Color::Green));
+        HalfBlockImage::new(&image).render(area, &mut buf);
+        assert_eq!(
+            cells(&buf),
+            [
+                ("▀", red(), blue()),
+                ("▄", blue(), Color::Green),
+                (" ", Color::Reset, Color::Green),
+            ]
+        );
+    }
+
+    #[test]
+    fn dithering_picks_colours_from_the_cube() {
+        let grey = [100, 100, 100, 255];
+        let image = image(4, 4, &[grey; 16]);
+        let area = Rect::new(0, 0, 4, 2);
+        let buf = render(HalfBlockImage::new(&image).dither(true), area, area);
+        let mut seen = Vec::new();
+        for cell in &buf.content {
+            for colour in [cell.fg, cell.bg] {
+                let Color::Rgb(r, g, b) = colour else {
+                    panic!("{colour:?}");
+                };
+                assert!(CUBE_LEVELS.contains(&r) && r == g && g == b, "{colour:?}");
+                seen.push(r);
+            }
+        }
+        // the pattern mixes the levels either side of the grey
+        assert!(seen.contains(&95) && seen.contains(&135), "{seen:?}");
+    }
+
+    #[test]
+    fn area_past_the_buffer_is_clipped() {
+        let image = image(1, 2, &[RED, BLUE]);
+        let buf = render(
+            HalfBlockImage::new(&image).fit(ImageFit::Stretch),
+            Rect::new(1, 0, 4, 3),
+            Rect::new(0, 0, 2, 1),
+        );
+        assert_eq!(
+            cells(&buf),
+            [(" ", Color::Reset, Color::Reset), ("▀", red(), red())]
+        );
+    }
+}
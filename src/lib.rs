@@ -1,8 +1,53 @@
+mod ansi_parser;
 mod bevy_backend;
+mod bitmap_font;
+mod box_drawing;
 mod components;
+mod context;
+mod diagnostics;
+mod error;
+mod export;
+mod image_widget;
+mod mirror;
+mod playback;
+#[cfg(unix)]
+mod pty;
+mod rat_widget;
+mod ratatui_app;
 mod ratatui_plugin;
+mod reactive;
+mod recording;
+mod row_text;
+mod screenshot;
+mod server;
+mod term_input;
+mod threaded_app;
+mod tileset;
 
-pub use bevy_backend::BevyBackend;
-
-pub use components::{CellComponent, FontStyle, RapidBlink, SlowBlink, TerminalComponent};
+pub use ansi_parser::AnsiParser;
+pub use bevy_backend::{BevyBackend, RenderMode, TerminalImage, TerminalSizing};
+pub use bitmap_font::{BitmapFont, BitmapFontError, BitmapFontLoader};
+pub use components::{
+    AtlasGlyph, CellComponent, FontStyle, GlyphSprite, RapidBlink, SlowBlink, TerminalComponent,
+};
+pub use context::{ContextError, DefaultTerminal, RatatuiContext, TerminalHandle, TerminalName};
+pub use diagnostics::TerminalDiagnosticsPlugin;
+pub use error::{TerminalError, TerminalErrorKind};
+pub use export::{export_ansi, export_html, export_text};
+pub use image_widget::{HalfBlockImage, ImageFit};
+pub use mirror::TerminalMirror;
+pub use playback::{
+    parse_cast, CastAsset, CastError, CastEvent, CastLoader, CastPlayer, CastSettings,
+};
+#[cfg(unix)]
+pub use pty::{key_to_bytes, PtyTerminal};
+pub use rat_widget::{RatWidget, StatefulRatWidget};
+pub use ratatui_app::{AppTerminal, RatatuiApp, RatatuiAppExt};
 pub use ratatui_plugin::RatatuiPlugin;
+pub use reactive::ReactiveRendering;
+pub use recording::CastRecorder;
+pub use screenshot::{ScreenshotError, TerminalRasterizer};
+pub use server::TerminalServer;
+pub use term_input::{InputDecoder, RemoteInput, TermEvent, TermKey, TermKeyEvent};
+pub use threaded_app::{ProxyBackend, TermEvents, ThreadedApp};
+pub use tileset::Tileset;
@@ -0,0 +1,46 @@
+//! Mirrors what a terminal draws to a real terminal, so the same ratatui UI shows up in the Bevy
+//! window and on stdout, over SSH or in the terminal the game was launched from.
+
+use std::{
+    fmt,
+    io::{self, Write},
+    sync::{Arc, Mutex},
+};
+
+/// Where mirrored output goes, set on a terminal with [`crate::BevyBackend::mirror_to`].
+#[derive(Clone)]
+pub struct TerminalMirror {
+    writer: Arc<Mutex<dyn Write + Send>>,
+}
+
+impl TerminalMirror {
+    pub fn new(writer: impl Write + Send + 'static) -> Self {
+        TerminalMirror {
+            writer: Arc::new(Mutex::new(writer)),
+        }
+    }
+
+    /// Mirrors to the stdout of the process.
+    pub fn stdout() -> Self {
+        TerminalMirror::new(io::stdout())
+    }
+
+    /// Writes ANSI output, errors are dropped so a closed pipe never stops the game.
+    pub fn write(&self, data: &str) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.write_all(data.as_bytes());
+        }
+    }
+
+    pub fn flush(&self) {
+        if let Ok(mut writer) = self.writer.lock() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+impl fmt::Debug for TerminalMirror {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TerminalMirror").finish_non_exhaustive()
+    }
+}
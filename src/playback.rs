@@ -0,0 +1,448 @@
+//! Plays asciinema casts and raw ANSI files back into a terminal. A [`CastAsset`] is loaded from
+//! a `.cast` file, or from `.ans`/`.ansi` files as a single frame, and a [`CastPlayer`] put on the
+//! terminal entity feeds its output through an [`AnsiParser`] as time passes. The output is laid
+//! out at the size it was recorded at, see [`CastSettings`] for ANSI files, and clipped to the
+//! terminal.
+
+use std::{error::Error, fmt};
+
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{ansi_parser::AnsiParser, components::TerminalComponent, ratatui_plugin::TermStatus};
+
+/// Terminal output with timestamps, see the module docs.
+#[derive(Asset, TypePath, Debug, Clone, Default)]
+pub struct CastAsset {
+    /// Size of the terminal the output was recorded on
+    pub width: u16,
+    pub height: u16,
+    /// (seconds since start, event) in time order
+    pub events: Vec<(f64, CastEvent)>,
+}
+
+/// Something that happened in a recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CastEvent {
+    /// Output written to the terminal
+    Output(String),
+    /// The terminal changing size, later output is laid out at the new size
+    Resize(u16, u16),
+}
+
+impl CastAsset {
+    /// Length of the recording in seconds.
+    pub fn duration(&self) -> f64 {
+        self.events.last().map_or(0.0, |(time, _)| *time)
+    }
+}
+
+/// Errors that can happen while loading a [`CastAsset`].
+#[derive(Debug)]
+pub enum CastError {
+    Io(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for CastError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CastError::Io(e) => write!(f, "could not read cast: {}", e),
+            CastError::Parse(e) => write!(f, "invalid cast: {}", e),
+        }
+    }
+}
+
+impl Error for CastError {}
+
+impl From<std::io::Error> for CastError {
+    fn from(e: std::io::Error) -> Self {
+        CastError::Io(e)
+    }
+}
+
+/// Parses an asciinema v2 cast, keeping the output and resize events.
+pub fn parse_cast(source: &str) -> Result<CastAsset, CastError> {
+    let mut lines = source.lines().filter(|line| !line.trim().is_empty());
+    let header = lines
+        .next()
+        .ok_or_else(|| CastError::Parse("missing header".to_string()))?;
+    let header: Value =
+        serde_json::from_str(header).map_err(|e| CastError::Parse(format!("bad header: {}", e)))?;
+    if header["version"].as_u64() != Some(2) {
+        return Err(CastError::Parse(
+            "only version 2 casts are supported".to_string(),
+        ));
+    }
+    let size = |key: &str, default: u16| {
+        header[key]
+            .as_u64()
+            .and_then(|n| u16::try_from(n).ok())
+            .unwrap_or(default)
+    };
+
+    let mut cast = CastAsset {
+        width: size("width", 80),
+        height: size("height", 24),
+        events: Vec::new(),
+    };
+
+    for (number, line) in lines.enumerate() {
+        let (time, code, data): (f64, String, String) = serde_json::from_str(line)
+            .map_err(|e| CastError::Parse(format!("bad event on line {}: {}", number + 2, e)))?;
+        match code.as_str() {
+            "o" => cast.events.push((time, CastEvent::Output(data))),
+            "r" => {
+                let size = data
+                    .split_once('x')
+                    .and_then(|(w, h)| Some((w.trim().parse().ok()?, h.trim().parse().ok()?)))
+                    .ok_or_else(|| {
+                        CastError::Parse(format!("bad size on line {}: {:?}", number + 2, data))
+                    })?;
+                cast.events.push((time, CastEvent::Resize(size.0, size.1)));
+            }
+            _ => {}
+        }
+    }
+    Ok(cast)
+}
+
+/// Loader settings, for the size raw ANSI files are laid out at. Pass them with
+/// `AssetServer::load_with_settings`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CastSettings {
+    pub ansi_width: u16,
+    pub ansi_height: u16,
+}
+
+impl Default for CastSettings {
+    fn default() -> Self {
+        // the size of a DOS screen, which most ANSI art is drawn for
+        CastSettings {
+            ansi_width: 80,
+            ansi_height: 25,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct CastLoader;
+
+impl AssetLoader for CastLoader {
+    type Asset = CastAsset;
+    type Settings = CastSettings;
+    type Error = CastError;
+
+    fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader,
+        settings: &'a CastSettings,
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+        Box::pin(async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let source = String::from_utf8_lossy(&bytes);
+
+            let path = load_context.path().to_string_lossy().to_lowercase();
+            if path.ends_with(".cast") {
+                parse_cast(&source)
+            } else {
+                // a raw ANSI file is one frame shown straight away
+                Ok(CastAsset {
+                    width: settings.ansi_width,
+                    height: settings.ansi_height,
+                    events: vec![(
+                        0.0,
+                        CastEvent::Output(source.replace("\r\n", "\n").replace('\n', "\r\n")),
+                    )],
+                })
+            }
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cast", "ans", "ansi"]
+    }
+}
+
+/// Plays a [`CastAsset`] into the terminal on the same entity. Don't draw to that terminal
+/// yourself while it plays, the cast writes straight into the backend.
+#[derive(Component, Debug, Clone)]
+pub struct CastPlayer {
+    pub cast: Handle<CastAsset>,
+    /// Playback rate, 1.0 is real time
+    pub speed: f32,
+    pub paused: bool,
+    /// Start over when the end is reached
+    pub looping: bool,
+    time: f64,
+    next_event: usize,
+    restart: bool,
+    parser: AnsiParser,
+}
+
+impl CastPlayer {
+    pub fn new(cast: Handle<CastAsset>) -> Self {
+        CastPlayer {
+            cast,
+            speed: 1.0,
+            paused: false,
+            looping: false,
+            time: 0.0,
+            next_event: 0,
+            restart: true,
+            parser: AnsiParser::new(),
+        }
+    }
+
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.max(0.0);
+    }
+
+    /// Current position in seconds.
+    pub fn time(&self) -> f64 {
+        self.time
+    }
+
+    /// Jumps to a position in seconds. Seeking backwards clears the terminal and replays the
+    /// output up to that point at once.
+    pub fn seek(&mut self, time: f64) {
+        let time = time.max(0.0);
+        if time < self.time {
+            self.restart = true;
+        }
+        self.time = time;
+    }
+}
+
+pub(crate) fn play_casts(
+    time: Res<Time>,
+    casts: Res<Assets<CastAsset>>,
+    mut players: Query<(&mut CastPlayer, &mut TerminalComponent, &TermStatus)>,
+) {
+    for (mut player, mut termy, status) in players.iter_mut() {
+        if !status.ready() {
+            continue;
+        }
+        let Some(cast) = casts.get(&player.cast) else {
+            continue;
+        };
+        let player = &mut *player;
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+
+        if !player.paused {
+            player.time += time.delta_seconds_f64() * player.speed as f64;
+        }
+        if player.looping && player.time > cast.duration() && player.next_event >= cast.events.len()
+        {
+            player.time = 0.0;
+            player.restart = true;
+        }
+
+        if player.restart {
+            AnsiParser::new().feed("\x1b[H\x1b[2J", termy_backend);
+            player.parser = sized_parser(cast.width, cast.height);
+            player.next_event = 0;
+            player.restart = false;
+        }
+
+        while let Some((at, event)) = cast.events.get(player.next_event) {
+            if *at > player.time {
+                break;
+            }
+            match event {
+                CastEvent::Output(data) => player.parser.feed(data, termy_backend),
+                CastEvent::Resize(width, height) => {
+                    player.parser = sized_parser(*width, *height);
+                }
+            }
+            player.next_event += 1;
+        }
+    }
+}
+
+/// A parser laying output out at the recorded size, or following the terminal without one.
+fn sized_parser(width: u16, height: u16) -> AnsiParser {
+    if width > 0 && height > 0 {
+        AnsiParser::with_size(width, height)
+    } else {
+        AnsiParser::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Modifier, Style},
+        text::{Line, Span},
+        widgets::Paragraph,
+        Terminal,
+    };
+
+    use super::*;
+    use crate::BevyBackend;
+
+    /// Plays a whole cast into a fresh terminal of the given size and returns its screen.
+    fn play(cast: CastAsset, width: u16, height: u16) -> Buffer {
+        let mut world = World::new();
+        world.insert_resource(Time::<()>::default());
+        let mut casts = Assets::<CastAsset>::default();
+        let mut player = CastPlayer::new(casts.add(cast));
+        player.seek(f64::MAX);
+        world.insert_resource(casts);
+
+        let mut backend = BevyBackend::default();
+        backend.resize(width, height);
+        let terminal = world
+            .spawn((
+                player,
+                TerminalComponent {
+                    ratatui_terminal: Terminal::new(backend).unwrap(),
+                },
+                TermStatus::set_up(),
+            ))
+            .id();
+        world.run_system_once(play_casts);
+
+        let termy = world.get::<TerminalComponent>(terminal).unwrap();
+        termy.ratatui_terminal.backend().buffer.clone()
+    }
+
+    fn recorded() -> (Buffer, String) {
+        let mut backend = BevyBackend::default();
+        backend.resize(8, 3);
+        backend.start_recording();
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        for frame in ["first", "second"] {
+            terminal
+                .draw(|f| {
+                    let lines = vec![
+                        Line::from(Span::styled(
+                            frame,
+                            Style::default()
+                                .fg(Color::Red)
+                                .bg(Color::Rgb(1, 2, 3))
+                                .add_modifier(Modifier::BOLD),
+                        )),
+                        Line::from("世界 \"q\""),
+                        Line::from(Span::styled("end", Style::default().fg(Color::Indexed(99)))),
+                    ];
+                    f.render_widget(Paragraph::new(lines), f.size());
+                })
+                .unwrap();
+        }
+
+        let backend = terminal.backend_mut();
+        let cast = backend.stop_recording().unwrap().to_cast();
+        (backend.buffer.clone(), cast)
+    }
+
+    #[test]
+    fn recording_plays_back_the_same_screen() {
+        let (screen, cast) = recorded();
+        let cast = parse_cast(&cast).unwrap();
+        assert_eq!((cast.width, cast.height), (8, 3));
+        assert!(cast.events.len() >= 3);
+
+        assert_eq!(play(cast, 8, 3), screen);
+    }
+
+    #[test]
+    fn smaller_terminal_shows_the_clipped_recording() {
+        let (screen, cast) = recorded();
+        let played = play(parse_cast(&cast).unwrap(), 5, 2);
+
+        for y in 0..2 {
+            for x in 0..5 {
+                assert_eq!(played.get(x, y), screen.get(x, y), "cell {x},{y}");
+            }
+        }
+    }
+
+    #[test]
+    fn output_wraps_at_the_recorded_width() {
+        let cast = CastAsset {
+            width: 3,
+            height: 2,
+            events: vec![(0.0, CastEvent::Output("abcdef".to_string()))],
+        };
+        let played = play(cast, 6, 3);
+        assert_eq!(
+            crate::export_text(&played, Rect::new(0, 0, 6, 3)),
+            "abc\ndef\n\n"
+        );
+    }
+
+    #[test]
+    fn parses_escapes_and_resizes_and_skips_input_events() {
+        let source = r#"{"version": 2, "width": 10, "height": 4, "env": {"TERM": "xterm"}}
+[0.5, "o", "a\u001b[1m\"😀\"\r\n"]
+
+[1.0, "i", "q"]
+[1.5, "r", "20x5"]
+"#;
+        let cast = parse_cast(source).unwrap();
+        assert_eq!((cast.width, cast.height), (10, 4));
+        assert_eq!(
+            cast.events,
+            [
+                (0.5, CastEvent::Output("a\x1b[1m\"😀\"\r\n".to_string())),
+                (1.5, CastEvent::Resize(20, 5)),
+            ]
+        );
+        assert_eq!(cast.duration(), 1.5);
+    }
+
+    #[test]
+    fn output_after_a_resize_wraps_at_the_new_width() {
+        let cast = CastAsset {
+            width: 3,
+            height: 2,
+            events: vec![
+                (0.0, CastEvent::Output("abc".to_string())),
+                (0.5, CastEvent::Resize(5, 2)),
+                (1.0, CastEvent::Output("\x1b[2;1Hdefgh".to_string())),
+            ],
+        };
+        let played = play(cast, 6, 3);
+        assert_eq!(
+            crate::export_text(&played, Rect::new(0, 0, 6, 3)),
+            "abc\ndefgh\n\n"
+        );
+    }
+
+    #[test]
+    fn bad_casts_are_errors() {
+        for source in [
+            "",
+            "not json",
+            "{\"version\": 1, \"width\": 80, \"height\": 24}",
+            "{\"version\": 2}\n[0.5, \"o\"]",
+            "{\"version\": 2}\n[0.5, \"o\", \"unterminated]",
+            "{\"version\": 2}\n[0.5, \"r\", \"80 by 24\"]",
+        ] {
+            assert!(
+                matches!(parse_cast(source), Err(CastError::Parse(_))),
+                "{source:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,307 @@
+//! Runs a shell or any other program on a pseudo terminal and shows it in a Bevy terminal. The
+//! program's output goes through an [`AnsiParser`] into the backend, the focused terminal gets the
+//! keyboard as bytes, and the pty is resized along with the grid.
+//!
+//! Only available on Unix, it needs `openpty` and there is no ConPTY support for Windows.
+
+use std::{
+    fs::File,
+    io::{self, Read, Write},
+    os::{
+        fd::{AsRawFd, FromRawFd},
+        unix::process::CommandExt,
+    },
+    process::{Child, Command, ExitStatus, Stdio},
+    sync::{
+        mpsc::{self, Receiver},
+        Mutex,
+    },
+    thread,
+};
+
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+
+use crate::{
+    ansi_parser::AnsiParser, components::TerminalComponent, ratatui_plugin::TermStatus,
+    term_input::TermKeyEvent,
+};
+
+/// A program running on a pseudo terminal, put it on the entity of the terminal showing it. Only
+/// available on Unix.
+#[derive(Component)]
+pub struct PtyTerminal {
+    /// Keyboard input is only sent to the focused terminal. One pty has focus at a time,
+    /// focusing another or spawning a new one takes it away from the rest
+    pub focused: bool,
+    /// Set once the program has exited
+    pub exit_status: Option<ExitStatus>,
+    parser: AnsiParser,
+    master: File,
+    output: Mutex<Receiver<Vec<u8>>>,
+    /// Bytes of a UTF-8 character split across reads
+    pending: Vec<u8>,
+    child: Child,
+    size: (u16, u16),
+}
+
+fn winsize(columns: u16, rows: u16) -> libc::winsize {
+    libc::winsize {
+        ws_row: rows,
+        ws_col: columns,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    }
+}
+
+fn check(ret: libc::c_int) -> io::Result<libc::c_int> {
+    if ret < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
+
+impl PtyTerminal {
+    /// Starts the command on a new pty, it gets resized to the grid on the next frame.
+    pub fn spawn(mut command: Command) -> io::Result<Self> {
+        let (mut master_fd, mut slave_fd) = (0, 0);
+        let size = winsize(80, 24);
+        check(unsafe {
+            libc::openpty(
+                &mut master_fd,
+                &mut slave_fd,
+                std::ptr::null_mut(),
+                std::ptr::null(),
+                &size,
+            )
+        })?;
+        let master = unsafe { File::from_raw_fd(master_fd) };
+        let slave = unsafe { File::from_raw_fd(slave_fd) };
+        // the child must not keep the master open, or we never see it hang up
+        check(unsafe { libc::fcntl(master_fd, libc::F_SETFD, libc::FD_CLOEXEC) })?;
+
+        command
+            .stdin(Stdio::from(slave.try_clone()?))
+            .stdout(Stdio::from(slave.try_clone()?))
+            .stderr(Stdio::from(slave))
+            .env("TERM", "xterm-256color");
+        unsafe {
+            command.pre_exec(|| {
+                // new session with the pty as its controlling terminal
+                check(libc::setsid())?;
+                check(libc::ioctl(0, libc::TIOCSCTTY as _, 0))?;
+                Ok(())
+            });
+        }
+        let child = command.spawn()?;
+
+        let (sender, receiver) = mpsc::channel();
+        let mut reader = master.try_clone()?;
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            // reading fails with EIO once the program is gone
+            while let Ok(n @ 1..) = reader.read(&mut buf) {
+                if sender.send(buf[..n].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(PtyTerminal {
+            focused: true,
+            exit_status: None,
+            parser: AnsiParser::new(),
+            master,
+            output: Mutex::new(receiver),
+            pending: Vec::new(),
+            child,
+            size: (80, 24),
+        })
+    }
+
+    /// Sends bytes to the program as if they were typed.
+    pub fn write(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.master.write_all(bytes)
+    }
+
+    /// Kills the program, it is also killed when the component is dropped.
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.child.kill()
+    }
+
+    /// Resizes the pty, the program gets a SIGWINCH and redraws.
+    pub fn resize(&mut self, columns: u16, rows: u16) -> io::Result<()> {
+        let size = winsize(columns, rows);
+        check(unsafe { libc::ioctl(self.master.as_raw_fd(), libc::TIOCSWINSZ, &size) })?;
+        self.size = (columns, rows);
+        Ok(())
+    }
+
+    /// Output received since the last call, decoded as far as it is valid UTF-8.
+    fn take_output(&mut self) -> String {
+        if let Ok(output) = self.output.lock() {
+            self.pending.extend(output.try_iter().flatten());
+        }
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(_) => self.pending.len(),
+            // keep a character that was cut off, replace anything actually invalid
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => self.pending.len(),
+        };
+        let text = String::from_utf8_lossy(&self.pending[..valid]).into_owned();
+        self.pending.drain(..valid);
+        text
+    }
+}
+
+impl Drop for PtyTerminal {
+    fn drop(&mut self) {
+        if self.exit_status.is_none() {
+            let _ = self.child.kill();
+            let _ = self.child.wait();
+        }
+    }
+}
+
+/// The bytes a terminal sends for a key press, None for keys it has no sequence for.
+pub fn key_to_bytes(key: &Key, ctrl: bool, alt: bool) -> Option<Vec<u8>> {
+    match key {
+        // text of several characters, from an input method or some layouts, goes as typed
+        Key::Character(text) if text.chars().nth(1).is_some() => Some(text.as_bytes().to_vec()),
+        _ => TermKeyEvent::from_bevy(key, ctrl, alt)?.to_bytes(),
+    }
+}
+
+/// Feeds program output into the terminals, forwards key presses to the focused one and keeps
+/// the pty size in step with the grid.
+pub(crate) fn update_pty_terminals(
+    mut ptys: Query<(
+        Entity,
+        &mut PtyTerminal,
+        &mut TerminalComponent,
+        &TermStatus,
+    )>,
+    mut keyboard: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut last_focused: Local<Option<Entity>>,
+) {
+    // a pty that was focused since the last frame takes the focus from the one that had it
+    let focused: Vec<Entity> = ptys
+        .iter()
+        .filter(|(_, pty, _, _)| pty.focused)
+        .map(|(e, _, _, _)| e)
+        .collect();
+    let focus = focused
+        .iter()
+        .find(|e| Some(**e) != *last_focused)
+        .or(focused.first())
+        .copied();
+    if focused.len() > 1 {
+        for (e, mut pty, _, _) in ptys.iter_mut() {
+            if pty.focused && Some(e) != focus {
+                pty.focused = false;
+            }
+        }
+    }
+    *last_focused = focus;
+
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let typed: Vec<Vec<u8>> = keyboard
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .filter_map(|event| key_to_bytes(&event.logical_key, ctrl, alt))
+        .collect();
+
+    for (_, mut pty, mut termy, status) in ptys.iter_mut() {
+        if !status.ready() {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+
+        let grid = (termy_backend.width, termy_backend.height);
+        if grid != pty.size {
+            let _ = pty.resize(grid.0, grid.1);
+        }
+
+        let output = pty.take_output();
+        if !output.is_empty() {
+            pty.parser.feed(&output, termy_backend);
+        }
+
+        if pty.focused && pty.exit_status.is_none() {
+            for bytes in &typed {
+                let _ = pty.write(bytes);
+            }
+        }
+
+        if pty.exit_status.is_none() {
+            pty.exit_status = pty.child.try_wait().ok().flatten();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::InputPlugin;
+    use ratatui::Terminal;
+
+    use super::*;
+    use crate::BevyBackend;
+
+    fn spawn_pty(world: &mut World) -> Entity {
+        let pty = PtyTerminal::spawn(Command::new("cat")).unwrap();
+        let terminal = TerminalComponent {
+            ratatui_terminal: Terminal::new(BevyBackend::default()).unwrap(),
+        };
+        world.spawn((pty, terminal, TermStatus::set_up())).id()
+    }
+
+    fn focused(world: &mut World) -> Vec<Entity> {
+        let mut ptys = world.query::<(Entity, &PtyTerminal)>();
+        ptys.iter(world)
+            .filter(|(_, pty)| pty.focused)
+            .map(|(e, _)| e)
+            .collect()
+    }
+
+    #[test]
+    fn focus_is_exclusive() {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin)
+            .add_systems(Update, update_pty_terminals);
+
+        let first = spawn_pty(&mut app.world);
+        app.update();
+        assert_eq!(focused(&mut app.world), [first]);
+
+        // a new pty takes the focus
+        let second = spawn_pty(&mut app.world);
+        app.update();
+        assert_eq!(focused(&mut app.world), [second]);
+
+        // and focusing the first one again takes it back
+        app.world.get_mut::<PtyTerminal>(first).unwrap().focused = true;
+        app.update();
+        assert_eq!(focused(&mut app.world), [first]);
+    }
+
+    #[test]
+    fn ctrl_and_alt_keys() {
+        let a = Key::Character("a".into());
+        assert_eq!(key_to_bytes(&a, false, false), Some(b"a".to_vec()));
+        assert_eq!(key_to_bytes(&a, true, false), Some(vec![1]));
+        assert_eq!(key_to_bytes(&a, false, true), Some(b"\x1ba".to_vec()));
+        assert_eq!(
+            key_to_bytes(&Key::ArrowUp, false, false),
+            Some(b"\x1b[A".to_vec())
+        );
+        assert_eq!(key_to_bytes(&Key::Shift, false, false), None);
+    }
+}
@@ -0,0 +1,164 @@
+//! Ratatui widgets as components. Spawn [`RatWidget`]s and [`StatefulRatWidget`]s as children of
+//! a terminal entity and the plugin draws them into it every frame, lowest `z` first, so each
+//! panel can be updated by its own systems without anyone calling `draw` by hand. Terminals
+//! without widget children are left alone.
+//!
+//! The widgets redraw their terminal every frame, so don't draw to it yourself. Terminals that
+//! already have a driver, a [`crate::ThreadedApp`], [`crate::CastPlayer`], `PtyTerminal` or
+//! [`crate::RatatuiApp`], keep it and their widgets are skipped with a [`crate::TerminalError`].
+//!
+//! Stateful widgets are only drawn with a `ListState` or `TableState`, a
+//! `StatefulRatWidget` of any other state is never drawn.
+
+use bevy::{prelude::*, utils::HashMap};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::{ListState, StatefulWidgetRef, TableState, WidgetRef},
+};
+
+#[cfg(unix)]
+use crate::pty::PtyTerminal;
+use crate::{
+    components::TerminalComponent,
+    error::{TerminalError, TerminalErrorKind},
+    playback::CastPlayer,
+    ratatui_app::AppDriven,
+    ratatui_plugin::TermStatus,
+    threaded_app::ThreadedApp,
+};
+
+/// Terminals something other than their widgets draws to.
+#[cfg(unix)]
+type Driven = Or<(
+    With<ThreadedApp>,
+    With<CastPlayer>,
+    With<AppDriven>,
+    With<PtyTerminal>,
+)>;
+#[cfg(not(unix))]
+type Driven = Or<(With<ThreadedApp>, With<CastPlayer>, With<AppDriven>)>;
+
+/// A widget drawn into the terminal its entity is a child of.
+#[derive(Component)]
+pub struct RatWidget {
+    /// Where the widget goes, clipped to the terminal
+    pub rect: Rect,
+    /// Widgets with a higher z are drawn over those with a lower one
+    pub z: i32,
+    pub widget: Box<dyn WidgetRef + Send + Sync>,
+}
+
+impl RatWidget {
+    pub fn new(rect: Rect, widget: impl WidgetRef + Send + Sync + 'static) -> Self {
+        RatWidget {
+            rect,
+            z: 0,
+            widget: Box::new(widget),
+        }
+    }
+
+    pub fn z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+}
+
+/// A `List` or `Table` along with its state, drawn like a [`RatWidget`]. Change the state to
+/// scroll or select, ratatui keeps its offset up to date when drawing. Only `ListState` and
+/// `TableState` widgets are drawn, those are the stateful widgets ratatui has.
+#[derive(Component)]
+pub struct StatefulRatWidget<S: Send + Sync + 'static> {
+    pub rect: Rect,
+    pub z: i32,
+    pub widget: Box<dyn StatefulWidgetRef<State = S> + Send + Sync>,
+    pub state: S,
+}
+
+impl<S: Send + Sync + 'static> StatefulRatWidget<S> {
+    pub fn new(
+        rect: Rect,
+        widget: impl StatefulWidgetRef<State = S> + Send + Sync + 'static,
+        state: S,
+    ) -> Self {
+        StatefulRatWidget {
+            rect,
+            z: 0,
+            widget: Box::new(widget),
+            state,
+        }
+    }
+
+    pub fn z(mut self, z: i32) -> Self {
+        self.z = z;
+        self
+    }
+
+    fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        self.widget
+            .render_ref(self.rect.intersection(area), buf, &mut self.state);
+    }
+}
+
+/// Draws the widget children of every terminal in z order.
+pub(crate) fn draw_widgets(
+    mut terminals: Query<(&mut TerminalComponent, &TermStatus, &Children)>,
+    driven: Query<(), Driven>,
+    mut errors: EventWriter<TerminalError>,
+    widgets: Query<(Entity, &Parent, &RatWidget)>,
+    mut lists: Query<(Entity, &Parent, &mut StatefulRatWidget<ListState>)>,
+    mut tables: Query<(Entity, &Parent, &mut StatefulRatWidget<TableState>)>,
+) {
+    // z of every widget by the terminal it belongs to, terminals also have all their cells as
+    // children so those are never looked at one by one
+    let mut by_terminal: HashMap<Entity, HashMap<Entity, i32>> = HashMap::new();
+    let all = widgets
+        .iter()
+        .map(|(e, p, w)| (e, p, w.z))
+        .chain(lists.iter().map(|(e, p, w)| (e, p, w.z)))
+        .chain(tables.iter().map(|(e, p, w)| (e, p, w.z)));
+    for (entity, parent, z) in all {
+        by_terminal
+            .entry(parent.get())
+            .or_default()
+            .insert(entity, z);
+    }
+
+    for (terminal, zs) in by_terminal {
+        if driven.contains(terminal) {
+            errors.send(TerminalError::new(
+                "draw_widgets",
+                TerminalErrorKind::DrivenTerminal(terminal),
+            ));
+            continue;
+        }
+        let Ok((mut termy, status, children)) = terminals.get_mut(terminal) else {
+            continue;
+        };
+        if !status.ready() {
+            continue;
+        }
+        // stable sort, so children with the same z are drawn in child order
+        let mut layers: Vec<(i32, Entity)> = children
+            .iter()
+            .filter_map(|child| Some((*zs.get(child)?, *child)))
+            .collect();
+        layers.sort_by_key(|(z, _)| *z);
+
+        let _ = termy.ratatui_terminal.draw(|frame| {
+            let area = frame.size();
+            let buf = frame.buffer_mut();
+            for (_, child) in &layers {
+                if let Ok((_, _, widget)) = widgets.get(*child) {
+                    widget
+                        .widget
+                        .render_ref(widget.rect.intersection(area), buf);
+                } else if let Ok((_, _, mut list)) = lists.get_mut(*child) {
+                    list.render(area, buf);
+                } else if let Ok((_, _, mut table)) = tables.get_mut(*child) {
+                    table.render(area, buf);
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,241 @@
+//! Elm style ratatui apps kept as a Bevy resource. Register one with
+//! [`RatatuiAppExt::add_ratatui_app`] and it gets drawn every frame into its terminal, gets the
+//! keyboard while its terminal is focused and resizes through [`RatatuiApp::on_event`] and ticks
+//! on `FixedUpdate`, so set `Time<Fixed>` for the tick rate you want.
+
+use std::marker::PhantomData;
+
+use bevy::{
+    app::AppExit,
+    input::{keyboard::KeyboardInput, ButtonState, InputSystem},
+    prelude::*,
+};
+use ratatui::{Frame, Terminal};
+
+use crate::{
+    components::TerminalComponent,
+    term_input::{RemoteInput, TermEvent, TermKeyEvent},
+    BevyBackend,
+};
+
+/// App state with the ratatui side of an app, see the module docs.
+pub trait RatatuiApp: Resource {
+    /// Draws the whole app, called every frame.
+    fn draw(&mut self, frame: &mut Frame);
+
+    /// Handles a key press or resize of the app's terminal.
+    fn on_event(&mut self, _event: TermEvent) {}
+
+    /// Called every fixed timestep.
+    fn on_tick(&mut self) {}
+
+    /// The Bevy app exits once this returns true.
+    fn should_quit(&self) -> bool {
+        false
+    }
+}
+
+/// Marks the terminal a [`RatatuiApp`] of type `T` draws into. Put it on a terminal you spawned
+/// yourself to pick its fonts and size, otherwise a default terminal is spawned for the app.
+#[derive(Component)]
+pub struct AppTerminal<T: RatatuiApp> {
+    /// The keyboard only goes to the app while one of its terminals is focused
+    pub focused: bool,
+    last_size: Option<(u16, u16)>,
+    app: PhantomData<fn() -> T>,
+}
+
+impl<T: RatatuiApp> Default for AppTerminal<T> {
+    fn default() -> Self {
+        AppTerminal {
+            focused: true,
+            last_size: None,
+            app: PhantomData,
+        }
+    }
+}
+
+/// Marks a terminal drawn by any [`RatatuiApp`], so widgets know to stay out of it.
+#[derive(Component)]
+pub(crate) struct AppDriven;
+
+/// Adds [`RatatuiApp`]s to a Bevy app.
+pub trait RatatuiAppExt {
+    /// Creates the app with `Default` or `FromWorld` and wires it up.
+    fn add_ratatui_app<T: RatatuiApp + FromWorld>(&mut self) -> &mut Self;
+
+    /// Wires up an app created by hand.
+    fn insert_ratatui_app<T: RatatuiApp>(&mut self, ratatui_app: T) -> &mut Self;
+}
+
+impl RatatuiAppExt for App {
+    fn add_ratatui_app<T: RatatuiApp + FromWorld>(&mut self) -> &mut Self {
+        self.init_resource::<T>();
+        add_ratatui_app_systems::<T>(self)
+    }
+
+    fn insert_ratatui_app<T: RatatuiApp>(&mut self, ratatui_app: T) -> &mut Self {
+        self.insert_resource(ratatui_app);
+        add_ratatui_app_systems::<T>(self)
+    }
+}
+
+fn add_ratatui_app_systems<T: RatatuiApp>(app: &mut App) -> &mut App {
+    app.add_event::<RemoteInput>()
+        .add_systems(PostStartup, spawn_app_terminal::<T>)
+        .add_systems(PreUpdate, app_events::<T>.after(InputSystem))
+        .add_systems(FixedUpdate, app_tick::<T>)
+        .add_systems(
+            Update,
+            (mark_app_terminals::<T>, draw_app::<T>, quit_app::<T>).chain(),
+        )
+}
+
+/// Spawns a default terminal for the app unless one was spawned for it at startup.
+fn spawn_app_terminal<T: RatatuiApp>(
+    terminals: Query<(), With<AppTerminal<T>>>,
+    mut commands: Commands,
+) {
+    if !terminals.is_empty() {
+        return;
+    }
+    if let Ok(mut terminal) = Terminal::new(BevyBackend::default()) {
+        let _ = terminal.clear();
+        commands.spawn((
+            TerminalComponent {
+                ratatui_terminal: terminal,
+            },
+            AppTerminal::<T>::default(),
+        ));
+    }
+}
+
+/// Hands the app the keyboard while it is focused, keys typed by remote clients of its terminal
+/// and resizes.
+fn app_events<T: RatatuiApp>(
+    mut ratatui_app: ResMut<T>,
+    mut terminals: Query<(Entity, &mut AppTerminal<T>, &TerminalComponent)>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut remote: EventReader<RemoteInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let remote: Vec<RemoteInput> = remote.read().copied().collect();
+
+    let mut focused = false;
+    for (entity, mut app_terminal, termy) in terminals.iter_mut() {
+        focused |= app_terminal.focused;
+        let termy_backend = termy.ratatui_terminal.backend();
+        let size = (termy_backend.width, termy_backend.height);
+        if app_terminal.last_size.is_some_and(|last| last != size) {
+            ratatui_app.on_event(TermEvent::Resize(size.0, size.1));
+        }
+        app_terminal.last_size = Some(size);
+
+        for input in remote.iter().filter(|input| input.terminal == entity) {
+            ratatui_app.on_event(TermEvent::Key(input.key));
+        }
+    }
+
+    for event in keyboard.read() {
+        if !focused || event.state != ButtonState::Pressed {
+            continue;
+        }
+        if let Some(key) = TermKeyEvent::from_bevy(&event.logical_key, ctrl, alt) {
+            ratatui_app.on_event(TermEvent::Key(key));
+        }
+    }
+}
+
+fn mark_app_terminals<T: RatatuiApp>(
+    terminals: Query<Entity, Added<AppTerminal<T>>>,
+    mut commands: Commands,
+) {
+    for entity in terminals.iter() {
+        commands.entity(entity).insert(AppDriven);
+    }
+}
+
+fn app_tick<T: RatatuiApp>(mut ratatui_app: ResMut<T>) {
+    ratatui_app.on_tick();
+}
+
+fn draw_app<T: RatatuiApp>(
+    mut ratatui_app: ResMut<T>,
+    mut terminals: Query<&mut TerminalComponent, With<AppTerminal<T>>>,
+) {
+    for mut termy in terminals.iter_mut() {
+        let _ = termy.ratatui_terminal.draw(|frame| ratatui_app.draw(frame));
+    }
+}
+
+fn quit_app<T: RatatuiApp>(ratatui_app: Res<T>, mut exit: EventWriter<AppExit>) {
+    if ratatui_app.should_quit() {
+        exit.send(AppExit);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::{keyboard::Key, InputPlugin};
+
+    use super::*;
+    use crate::TermKey;
+
+    #[derive(Resource, Default)]
+    struct Typed(Vec<TermKey>);
+
+    impl RatatuiApp for Typed {
+        fn draw(&mut self, _frame: &mut Frame) {}
+
+        fn on_event(&mut self, event: TermEvent) {
+            if let TermEvent::Key(key) = event {
+                self.0.push(key.key);
+            }
+        }
+    }
+
+    fn press(app: &mut App, c: &str) {
+        app.world.send_event(KeyboardInput {
+            key_code: KeyCode::KeyA,
+            logical_key: Key::Character(c.into()),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        app.update();
+    }
+
+    #[test]
+    fn keyboard_only_reaches_a_focused_app() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin))
+            .add_ratatui_app::<Typed>();
+        let terminal = app
+            .world
+            .spawn((
+                TerminalComponent {
+                    ratatui_terminal: Terminal::new(BevyBackend::default()).unwrap(),
+                },
+                AppTerminal::<Typed>::default(),
+            ))
+            .id();
+
+        press(&mut app, "a");
+        app.world
+            .get_mut::<AppTerminal<Typed>>(terminal)
+            .unwrap()
+            .focused = false;
+        press(&mut app, "b");
+        app.world
+            .get_mut::<AppTerminal<Typed>>(terminal)
+            .unwrap()
+            .focused = true;
+        press(&mut app, "c");
+
+        assert_eq!(
+            app.world.resource::<Typed>().0,
+            [TermKey::Char('a'), TermKey::Char('c')]
+        );
+    }
+}
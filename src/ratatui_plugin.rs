@@ -1,467 +1,1124 @@
 use bevy::{
     prelude::{Color as BevyColor, *},
     time::common_conditions::on_timer,
-    utils::{Duration, HashMap},
-    window::{PrimaryWindow, WindowResized, WindowResolution},
+    utils::{Duration, Instant},
+    window::{PrimaryWindow, RequestRedraw, WindowResized, WindowScaleFactorChanged},
 };
 
-use crate::components::{CellComponent, RapidBlink, SlowBlink, TerminalComponent};
-use crate::FontStyle;
+use crate::bitmap_font::{is_bitmap_font_path, BitmapFont, BitmapFontLoader};
+use crate::box_drawing::geometric_sheet;
+use crate::components::{
+    AtlasGlyph, CellComponent, GlyphSprite, RapidBlink, SlowBlink, TerminalComponent,
+};
+use crate::diagnostics::{finish_rebuild_times, SyncStats};
+use crate::error::{log_terminal_errors, TerminalError, TerminalErrorKind};
+use crate::playback::{play_casts, CastAsset, CastLoader};
+#[cfg(unix)]
+use crate::pty::{update_pty_terminals, PtyTerminal};
+use crate::rat_widget::draw_widgets;
+use crate::reactive::{request_redraws, terminals_drawn, wake_for_blink, ReactiveRendering};
+use crate::row_text::{spawn_rows, update_rows, RowText};
+use crate::server::{serve_terminals, TerminalServer};
+use crate::term_input::RemoteInput;
+use crate::threaded_app::{update_threaded_apps, ThreadedApp};
+use crate::{BevyBackend, FontStyle, RenderMode};
 
 ///Provides Bevy Plugin which creates terminal like window supporting Ratatui
 pub struct RatatuiPlugin;
 
+/// How often slow and rapid blinking cells blink.
+pub(crate) const SLOW_BLINK: Duration = Duration::from_millis(600);
+pub(crate) const RAPID_BLINK: Duration = Duration::from_millis(200);
+
 impl Plugin for RatatuiPlugin {
     fn build(&self, app: &mut App) {
-        app.init_state::<TermState>();
-        app.init_state::<TermSizing>();
+        app.init_asset::<BitmapFont>()
+            .init_asset_loader::<BitmapFontLoader>();
+        app.init_asset::<CastAsset>()
+            .init_asset_loader::<CastLoader>();
+        app.add_event::<RemoteInput>();
+        app.add_event::<TerminalError>();
+        app.add_event::<RequestRedraw>();
+        app.add_systems(Last, log_terminal_errors);
+
+        app.add_systems(StateTransition, apply_term_status);
+
+        app.add_systems(First, slow_blink_cells.run_if(on_timer(SLOW_BLINK)));
+        app.add_systems(First, rapid_blink_cells.run_if(on_timer(RAPID_BLINK)));
 
         app.add_systems(
             First,
-            slow_blink_cells.run_if(on_timer(Duration::from_secs_f32(0.6))),
+            font_setup
+                .after(query_term_for_init)
+                .run_if(any_terminal_in(TermState::NeedsFont)),
+        );
+        app.add_systems(
+            First,
+            clear_virtual_cells
+                .after(font_setup)
+                .run_if(any_terminal_in(TermState::NeedsClearing)),
         );
         app.add_systems(
             First,
-            rapid_blink_cells.run_if(on_timer(Duration::from_millis(200))),
+            init_virtual_cells
+                .after(clear_virtual_cells)
+                .run_if(any_terminal_in(TermState::NeedsIniting)),
         );
 
         app.add_systems(
             First,
-            (font_setup
-                .after(query_term_for_init)
-                .run_if(in_state(TermState::TermNeedsFont))),
+            do_first_resize.run_if(any_terminal_in(TermState::Ready)),
         );
+
+        app.add_systems(First, (despawn_terminal_parts, query_term_for_init).chain());
         app.add_systems(
             First,
-            (clear_virtual_cells
-                .after(font_setup)
-                .run_if(in_state(TermState::TermNeedsClearing))),
+            finish_rebuild_times
+                .before(clear_virtual_cells)
+                .run_if(resource_exists::<SyncStats>),
         );
+
         app.add_systems(
             First,
-            (init_virtual_cells
+            update_metrics
                 .after(clear_virtual_cells)
-                .run_if(in_state(TermState::TermNeedsIniting))),
+                .before(init_virtual_cells),
         );
-
         app.add_systems(
             First,
-            (do_first_resize.run_if(in_state(TermState::AllTermsInited)))
-                .run_if(in_state(TermSizing::TermNeedsFirstResize)),
+            update_geometric_glyphs
+                .after(update_metrics)
+                .before(init_virtual_cells),
+        );
+        app.add_systems(
+            Last,
+            update_metrics
+                .before(handle_primary_window_resize)
+                .before(handle_parent_node_resize),
+        );
+        app.add_systems(
+            Last,
+            (handle_bitmap_font_loaded).run_if(on_event::<AssetEvent<BitmapFont>>()),
+        );
+        app.add_systems(
+            Last,
+            (handle_scale_factor_change)
+                .after(update_metrics)
+                .run_if(on_event::<WindowScaleFactorChanged>()),
         );
-
-        app.add_systems(First, (query_term_for_init));
 
         app.add_systems(
             Last,
             (handle_primary_window_resize).run_if(on_event::<WindowResized>()),
         );
+        app.add_systems(
+            Last,
+            (handle_parent_node_resize).run_if(any_terminal_in(TermState::Ready)),
+        );
+        // added once, other systems are ordered against it
+        app.add_systems(
+            PostUpdate,
+            (update_ents_from_vcupdate).run_if(terminals_drawn),
+        );
+        #[cfg(unix)]
+        app.add_systems(
+            PostUpdate,
+            (update_pty_terminals)
+                .before(update_ents_from_vcupdate)
+                .before(serve_terminals)
+                .run_if(any_with_component::<PtyTerminal>)
+                .run_if(any_terminal_in(TermState::Ready)),
+        );
+        app.add_systems(
+            PostUpdate,
+            (play_casts)
+                .before(update_ents_from_vcupdate)
+                .before(serve_terminals)
+                .run_if(any_terminal_in(TermState::Ready)),
+        );
         app.add_systems(
             PostUpdate,
-            (update_ents_from_vcupdate).run_if(in_state(TermState::AllTermsInited)),
+            (update_threaded_apps)
+                .before(update_ents_from_vcupdate)
+                .before(serve_terminals)
+                .run_if(any_with_component::<ThreadedApp>)
+                .run_if(any_terminal_in(TermState::Ready)),
         );
         app.add_systems(
             PostUpdate,
-            (update_ents_from_vcupdate).run_if(in_state(TermState::TermNeedsIniting)),
+            (draw_widgets)
+                .before(update_ents_from_vcupdate)
+                .before(serve_terminals)
+                .run_if(any_terminal_in(TermState::Ready)),
         );
         app.add_systems(
-            Update,
-            (debug_entities).run_if(in_state(TermState::AllTermsInited)),
+            PostUpdate,
+            (serve_terminals)
+                .before(update_ents_from_vcupdate)
+                .run_if(any_with_component::<TerminalServer>)
+                .run_if(any_terminal_in(TermState::Ready)),
         );
 
         app.add_systems(
             First,
             (update_ents_from_comp)
                 .after(update_ents_from_vcupdate)
-                .run_if(in_state(TermState::AllTermsInited)),
+                .after(despawn_terminal_parts)
+                .run_if(any_terminal_in(TermState::Ready)),
+        );
+
+        app.add_systems(
+            First,
+            (update_rows)
+                .after(update_ents_from_vcupdate)
+                .after(despawn_terminal_parts)
+                .run_if(any_terminal_in(TermState::Ready)),
+        );
+
+        app.add_systems(
+            Last,
+            (update_terminal_images)
+                .after(handle_primary_window_resize)
+                .after(handle_parent_node_resize)
+                .run_if(any_terminal_in(TermState::Ready)),
+        );
+
+        app.add_systems(
+            Last,
+            (request_redraws, wake_for_blink)
+                .after(handle_primary_window_resize)
+                .after(handle_parent_node_resize)
+                .after(handle_scale_factor_change)
+                .after(handle_bitmap_font_loaded)
+                .run_if(resource_exists::<ReactiveRendering>),
         );
 
         app.add_systems(
             Last,
             (update_cursor)
                 .after(handle_primary_window_resize)
-                .run_if(in_state(TermState::AllTermsInited))
-                .run_if(in_state(TermSizing::TermGood)),
+                .run_if(any_terminal_in(TermState::Ready)),
         );
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
-enum TermState {
-    #[default]
-    NoTermsInited,
-    TermNeedsFont,
-    TermNeedsClearing,
-
-    TermNeedsIniting,
-    AllTermsInited,
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum TermState {
+    NeedsFont,
+    NeedsClearing,
+    NeedsIniting,
+    Ready,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, States)]
-enum TermSizing {
-    #[default]
-    NoTermsInited,
-
-    TermNeedsFirstResize,
-    TermGood,
+/// How far a terminal is through being set up. Every terminal gets its own, so one spawned later
+/// doesn't hold up or rebuild the terminals that are already running.
+#[derive(Component, Debug)]
+pub(crate) struct TermStatus {
+    pub(crate) state: TermState,
+    /// The state the terminal moves to on the next state transition, like `NextState`
+    pub(crate) next: Option<TermState>,
+    /// The window was sized to the terminal and its cursor spawned
+    sized: bool,
 }
 
-fn do_first_resize(
-    mut commands: Commands,
-    mut windows: Query<&mut Window>,
-    mut terminal_query: Query<(&Node, Entity, &mut TerminalComponent)>,
-    mut resize_state: ResMut<NextState<TermSizing>>,
-) {
-    let mut window = windows.single_mut();
-
-    let (nodik, e, mut termy) = terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend");
-
-    let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
-
-    let rat_term = &mut termy.ratatui_terminal;
-    let termy_backend = rat_term.backend_mut();
-    let rows = termy_backend.height;
-    let columns = termy_backend.width;
-
-    let node_size = nodik.size();
+impl TermStatus {
+    /// The cells are laid out and can be drawn to.
+    pub(crate) fn ready(&self) -> bool {
+        self.state == TermState::Ready
+    }
 
-    window
-        .resolution
-        .set(node_size.x * columns as f32, node_size.y * rows as f32);
-    //spawn the cursor
+    fn set(&mut self, state: TermState) {
+        self.next = Some(state);
+    }
 
-    let cursor_cell = commands
-        .spawn((TextBundle::from_section(" ", ns).with_style(Style {
-            top: Val::Px(termy_backend.cursor_pos.1 as f32 * node_size.y),
-            left: Val::Px(termy_backend.cursor_pos.0 as f32 * node_size.x),
+    /// Lays the cells out again, a terminal still waiting on its fonts gets there by itself.
+    fn rebuild(&mut self) {
+        if self.state != TermState::NeedsFont {
+            self.set(TermState::NeedsClearing);
+        }
+    }
+}
 
-            ..default()
-        }),))
-        .id();
+#[cfg(test)]
+impl TermStatus {
+    /// A terminal that is done being set up, for tests that run systems on their own.
+    pub(crate) fn set_up() -> Self {
+        TermStatus {
+            state: TermState::Ready,
+            next: None,
+            sized: true,
+        }
+    }
+}
 
-    termy_backend.cursor_ref = cursor_cell;
+/// Moves every terminal to the state it asked for, once a frame like the app states.
+fn apply_term_status(mut statuses: Query<&mut TermStatus>) {
+    for mut status in statuses.iter_mut() {
+        if let Some(next) = status.next.take() {
+            status.state = next;
+        }
+    }
+}
 
-    resize_state.set(TermSizing::TermGood);
+/// True when any terminal is in `state`, the system then only touches the ones that are.
+fn any_terminal_in(state: TermState) -> impl FnMut(Query<&TermStatus>) -> bool + Clone {
+    move |statuses: Query<&TermStatus>| statuses.iter().any(|status| status.state == state)
 }
 
-fn slow_blink_cells(mut slow_blink_query: Query<((&mut Text, &BackgroundColor, &mut SlowBlink))>) {
-    for (mut text, bgc, mut sb) in slow_blink_query.iter_mut() {
-        let mut section = text.sections.pop().unwrap();
+/// Marks the entities the plugin spawns for a terminal as its children: cells, the cursor, placed
+/// images and the measuring node. They are despawned along with the terminal.
+#[derive(Component)]
+pub(crate) struct TerminalPart;
+
+/// The hidden node of a terminal that is measured for the cell size.
+#[derive(Component)]
+struct CellMeasure;
+
+type CellQueryItem<'a> = (
+    Entity,
+    &'a CellComponent,
+    &'a Parent,
+    &'a mut Text,
+    &'a mut BackgroundColor,
+    Option<&'a SlowBlink>,
+    Option<&'a RapidBlink>,
+    Option<&'a Children>,
+);
+
+type GlyphQueryItem<'a> = (
+    &'a mut TextureAtlas,
+    &'a mut UiImage,
+    &'a mut BackgroundColor,
+    &'a mut Visibility,
+);
 
-        if sb.in_blink {
-            sb.in_blink = false;
-            section.style.color = bgc.0;
-        } else {
-            sb.in_blink = true;
-            section.style.color = sb.true_color.clone();
+fn do_first_resize(
+    mut commands: Commands,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+    mut terminal_query: Query<(Entity, &mut TerminalComponent, &mut TermStatus)>,
+    mut errors: EventWriter<TerminalError>,
+) {
+    for (e, mut termy, mut status) in terminal_query.iter_mut() {
+        if !status.ready() || status.sized {
+            continue;
+        }
+        let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
+
+        let rat_term = &mut termy.ratatui_terminal;
+        let termy_backend = rat_term.backend_mut();
+
+        // an embedded terminal lives inside someone else's layout, so the window is left alone
+        let grid = termy_backend.physical_grid_size();
+        if !termy_backend.embedded && grid.x > 0 && grid.y > 0 {
+            match windows.get_single_mut() {
+                Ok(mut window) => window.resolution.set_physical_resolution(grid.x, grid.y),
+                Err(_) => {
+                    errors.send(TerminalError::new(
+                        "do_first_resize",
+                        TerminalErrorKind::NoWindow,
+                    ));
+                }
+            }
         }
+        //spawn the cursor
+
+        let cursor_pos =
+            termy_backend.cell_position(termy_backend.cursor_pos.0, termy_backend.cursor_pos.1);
+        let cursor_cell = commands
+            .spawn((
+                TextBundle::from_section(" ", ns).with_style(Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(cursor_pos.y),
+                    left: Val::Px(cursor_pos.x),
+
+                    ..default()
+                }),
+                TerminalPart,
+            ))
+            .set_parent(e)
+            .id();
+
+        termy_backend.cursor_ref = cursor_cell;
+        status.sized = true;
+    }
+}
 
-        text.sections.push(section);
+fn slow_blink_cells(
+    mut slow_blink_query: Query<
+        (
+            &mut Text,
+            &BackgroundColor,
+            &mut SlowBlink,
+            Option<&Children>,
+        ),
+        Without<GlyphSprite>,
+    >,
+    mut glyph_query: Query<&mut BackgroundColor, With<GlyphSprite>>,
+) {
+    for (mut text, bgc, mut sb, children) in slow_blink_query.iter_mut() {
+        let color = if sb.in_blink { sb.true_color } else { bgc.0 };
+        sb.in_blink = !sb.in_blink;
+        blink_cell(&mut text, children, &mut glyph_query, color);
     }
 }
 
 fn rapid_blink_cells(
-    mut rapid_blink_query: Query<((&mut Text, &BackgroundColor, &mut RapidBlink))>,
+    mut rapid_blink_query: Query<
+        (
+            &mut Text,
+            &BackgroundColor,
+            &mut RapidBlink,
+            Option<&Children>,
+        ),
+        Without<GlyphSprite>,
+    >,
+    mut glyph_query: Query<&mut BackgroundColor, With<GlyphSprite>>,
 ) {
-    for (mut text, bgc, mut sb) in rapid_blink_query.iter_mut() {
-        let mut section = text.sections.pop().unwrap();
+    for (mut text, bgc, mut rb, children) in rapid_blink_query.iter_mut() {
+        let color = if rb.in_blink { rb.true_color } else { bgc.0 };
+        rb.in_blink = !rb.in_blink;
+        blink_cell(&mut text, children, &mut glyph_query, color);
+    }
+}
 
-        if sb.in_blink {
-            sb.in_blink = false;
-            section.style.color = bgc.0;
-        } else {
-            sb.in_blink = true;
-            section.style.color = sb.true_color.clone();
-        }
+/// Recolours the text of a cell, and its glyph sprite if it is drawn from an atlas.
+fn blink_cell(
+    text: &mut Text,
+    children: Option<&Children>,
+    glyph_query: &mut Query<&mut BackgroundColor, With<GlyphSprite>>,
+    color: BevyColor,
+) {
+    if let Some(section) = text.sections.last_mut() {
+        section.style.color = color;
+    }
 
-        text.sections.push(section);
+    for child in children.into_iter().flatten() {
+        if let Ok(mut tint) = glyph_query.get_mut(*child) {
+            tint.0 = color;
+        }
     }
 }
 
+/// Starts setting up the terminals spawned since the last frame, terminals can be spawned any
+/// time.
 fn query_term_for_init(
-    mut terminal_query: Query<(&mut TerminalComponent)>,
-    mut app_state: ResMut<NextState<TermState>>,
-    mut resize_state: ResMut<NextState<TermSizing>>,
+    mut commands: Commands,
+    mut terminal_query: Query<(Entity, &mut TerminalComponent), Without<TermStatus>>,
 ) {
-    let mut termy = &mut terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend")
-        .ratatui_terminal;
-    let termy_backend = termy.backend_mut();
+    for (e, mut termy) in terminal_query.iter_mut() {
+        termy.ratatui_terminal.backend_mut().bevy_initialized = true;
+        commands.entity(e).insert(TermStatus {
+            state: TermState::NeedsFont,
+            next: None,
+            sized: false,
+        });
+    }
+}
 
-    if (termy_backend.bevy_initialized == false) {
-        app_state.set(TermState::TermNeedsFont);
-        resize_state.set(TermSizing::TermNeedsFirstResize);
-        termy_backend.bevy_initialized = true;
+/// Despawns what the plugin spawned for terminals that were despawned, or lost their terminal
+/// component. An entity that gets a terminal component again is set up fresh.
+fn despawn_terminal_parts(
+    mut commands: Commands,
+    mut removed: RemovedComponents<TerminalComponent>,
+    parts: Query<(Entity, &Parent), With<TerminalPart>>,
+) {
+    let removed: Vec<Entity> = removed.read().collect();
+    if removed.is_empty() {
+        return;
+    }
+    for (part, parent) in parts.iter() {
+        if removed.contains(&parent.get()) {
+            commands.entity(part).despawn_recursive();
+        }
+    }
+    for entity in removed {
+        if let Some(mut entity) = commands.get_entity(entity) {
+            entity.remove::<TermStatus>();
+        }
     }
 }
 
 fn clear_virtual_cells(
     mut commands: Commands,
-    mut terminal_query: Query<(Entity, &mut TerminalComponent)>,
-    mut app_state: ResMut<NextState<TermState>>,
+    mut terminal_query: Query<(Entity, &mut TerminalComponent, &mut TermStatus)>,
+    measure_query: Query<(Entity, &Parent), With<CellMeasure>>,
+    bitmap_fonts: Res<Assets<BitmapFont>>,
+    mut stats: Option<ResMut<SyncStats>>,
 ) {
-    let (e, mut termy) = terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend");
-
-    let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
-    let rat_term = &mut termy.ratatui_terminal;
-    let termy_backend = rat_term.backend_mut();
-
-    for (_, entity) in termy_backend.entity_map.iter() {
-        commands.entity(*entity).despawn();
-    }
-    termy_backend.entity_map = HashMap::new();
-
-    // spawn a default node for the terminal to reference
-    commands.entity(e).insert(
-        TextBundle::from_section("T", ns) // Set the justification of the Text
-            .with_background_color(BevyColor::DARK_GRAY)
-            .with_text_justify(JustifyText::Center)
-            .with_style(Style {
-                display: Display::Grid,
-                position_type: PositionType::Absolute,
-                align_items: AlignItems::Stretch,
-                margin: UiRect::ZERO,
-                padding: UiRect::ZERO,
-                border: UiRect::ZERO,
-                grid_auto_flow: GridAutoFlow::Column,
-                top: Val::Px(-30.0),
-                left: Val::Px(-30.0),
+    for (e, mut termy, mut status) in terminal_query.iter_mut() {
+        if status.state != TermState::NeedsClearing {
+            continue;
+        }
+        if let Some(stats) = &mut stats {
+            // a rebuild that starts over before it finished is timed from its first clear
+            stats.rebuild_started.entry(e).or_insert_with(Instant::now);
+        }
+        let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
+        // a bitmap font gives the cell size directly, a TTF has to be measured
+        let bitmap_cell = termy
+            .get_bitmap_font(FontStyle::Normal)
+            .and_then(|handle| bitmap_fonts.get(handle))
+            .map(|bitmap| bitmap.glyph_size.as_vec2());
+        let rat_term = &mut termy.ratatui_terminal;
+        let termy_backend = rat_term.backend_mut();
+
+        let rows = termy_backend
+            .row_entities
+            .drain(..)
+            .flat_map(|(background, text)| [background, text]);
+        for entity in termy_backend.cell_entities.drain(..).chain(rows) {
+            if let Some(entity) = commands.get_entity(entity) {
+                entity.despawn_recursive();
+            }
+        }
+        termy_backend.cell_columns = 0;
 
+        // the terminal is a node covering the window, or its parent when embedded, that the cells
+        // are laid out in
+        commands.entity(e).insert(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
                 ..default()
-            }),
-    );
+            },
+            ..default()
+        });
+
+        // a node for the terminal to reference, it is only measured and never drawn
+        let measure_node = TextBundle {
+            visibility: Visibility::Hidden,
+            ..TextBundle::from_section("T", ns) // Set the justification of the Text
+                .with_background_color(BevyColor::DARK_GRAY)
+                .with_text_justify(JustifyText::Center)
+                .with_style(Style {
+                    display: Display::Grid,
+                    position_type: PositionType::Absolute,
+                    align_items: AlignItems::Stretch,
+                    margin: UiRect::ZERO,
+                    padding: UiRect::ZERO,
+                    border: UiRect::ZERO,
+                    grid_auto_flow: GridAutoFlow::Column,
+                    top: Val::Px(-30.0),
+                    left: Val::Px(-30.0),
+                    width: bitmap_cell
+                        .map_or(Val::Auto, |size| Val::Px(size.x * termy_backend.font_scale)),
+                    height: bitmap_cell
+                        .map_or(Val::Auto, |size| Val::Px(size.y * termy_backend.font_scale)),
+
+                    ..default()
+                })
+        };
+        let measure = measure_query
+            .iter()
+            .find(|(_, parent)| parent.get() == e)
+            .map(|(measure, _)| measure);
+        match measure {
+            Some(measure) => {
+                commands.entity(measure).insert(measure_node);
+            }
+            None => {
+                commands
+                    .spawn((measure_node, CellMeasure, TerminalPart))
+                    .set_parent(e);
+            }
+        }
 
-    app_state.set(TermState::TermNeedsIniting);
+        status.set(TermState::NeedsIniting);
+    }
 }
 
+/// Moves, restyles and shows or hides the cursor, only touching what changed so an idle terminal
+/// doesn't relayout its cursor every frame.
 fn update_cursor(
-    terminal_query: Query<(&Node, Entity, &TerminalComponent)>,
+    terminal_query: Query<(&TerminalComponent, &TermStatus)>,
+    mut cursor_query: Query<(&mut Style, &mut Text, &mut Visibility)>,
+    mut errors: EventWriter<TerminalError>,
+) {
+    for (termy, status) in terminal_query.iter() {
+        if !status.ready() || !status.sized {
+            continue;
+        }
+        let ns = termy.get_text_style(BevyColor::GREEN, FontStyle::Normal);
+        let rat_term = &termy.ratatui_terminal;
+        let termy_backend = rat_term.backend();
+        let cursor_pos =
+            termy_backend.cell_position(termy_backend.cursor_pos.0, termy_backend.cursor_pos.1);
+
+        let Ok((mut style, mut text, mut visibility)) =
+            cursor_query.get_mut(termy_backend.cursor_ref)
+        else {
+            errors.send(TerminalError::new(
+                "update_cursor",
+                TerminalErrorKind::MissingEntity(termy_backend.cursor_ref),
+            ));
+            continue;
+        };
 
-    mut commands: Commands,
+        let (top, left) = (Val::Px(cursor_pos.y), Val::Px(cursor_pos.x));
+        if style.top != top || style.left != left {
+            style.top = top;
+            style.left = left;
+        }
+        if !matches!(text.sections.as_slice(), [section] if same_text_style(&section.style, &ns)) {
+            *text = Text::from_section(" ", ns);
+        }
+        let wanted = if termy_backend.cursor {
+            Visibility::Visible
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
+
+/// Measures the reference node of the terminal and records the cell size at the current scale
+/// factor, so everything else can place cells on whole physical pixels. If the cell size changes
+/// after the cells were laid out (a font finished loading, the font was rescaled) they are rebuilt.
+fn update_metrics(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    measure_query: Query<(&Node, &Parent), With<CellMeasure>>,
+    mut terminal_query: Query<(&mut TerminalComponent, &mut TermStatus)>,
 ) {
-    let (nodik, e, termy) = terminal_query
-        .get_single()
-        .expect("More than one terminal with a bevybackend");
-    let ns = termy.get_text_style(BevyColor::GREEN, FontStyle::Normal);
-    let rat_term = &termy.ratatui_terminal;
-    let termy_backend = rat_term.backend();
-    let node_size = nodik.size();
-
-    commands
-        .entity(termy_backend.cursor_ref)
-        .insert((TextBundle::from_section(" ", ns).with_style(Style {
-            top: Val::Px(termy_backend.cursor_pos.1 as f32 * node_size.y),
-            left: Val::Px(termy_backend.cursor_pos.0 as f32 * node_size.x),
+    let scale_factor = windows.get_single().map_or(1.0, |w| w.scale_factor());
 
-            ..default()
-        }),));
+    for (nodik, parent) in measure_query.iter() {
+        let Ok((mut termy, mut status)) = terminal_query.get_mut(parent.get()) else {
+            continue;
+        };
+        let measured = nodik.size();
+        if measured.x <= 0.0 || measured.y <= 0.0 {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend();
+        let snapped = termy_backend.snapped_cell_size(measured, scale_factor);
+        if termy_backend.cell_size != snapped || termy_backend.scale_factor != scale_factor {
+            termy
+                .ratatui_terminal
+                .backend_mut()
+                .set_metrics(measured, scale_factor);
+
+            if status.ready() {
+                status.rebuild();
+            }
+        }
+    }
+}
+
+/// Redraws the procedural box drawing glyphs whenever the physical cell size changes, so they
+/// always fill the cell exactly.
+fn update_geometric_glyphs(
+    mut terminal_query: Query<&mut TerminalComponent>,
+    mut bitmap_fonts: ResMut<Assets<BitmapFont>>,
+    mut images: ResMut<Assets<Image>>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    for mut termy in terminal_query.iter_mut() {
+        let termy_backend = termy.ratatui_terminal.backend();
+        let size = termy_backend.physical_cell_size();
+        if !termy_backend.geometric_glyphs || size.x == 0 || size.y == 0 {
+            continue;
+        }
+        let current = termy_backend
+            .geometric_font
+            .as_ref()
+            .and_then(|handle| bitmap_fonts.get(handle));
+        if current.is_some_and(|font| font.glyph_size == size) {
+            continue;
+        }
 
-    if termy_backend.cursor {
-        commands
-            .entity(termy_backend.cursor_ref)
-            .insert(Visibility::Visible);
-    } else {
-        commands
-            .entity(termy_backend.cursor_ref)
-            .insert(Visibility::Hidden);
+        let sheet = geometric_sheet(size);
+        let (image, layout) = sheet.to_atlas();
+        let font = BitmapFont {
+            glyph_size: sheet.glyph_size,
+            glyphs: sheet.glyphs,
+            coverage: sheet.coverage,
+            image: images.add(image),
+            layout: layouts.add(layout),
+        };
+        termy.ratatui_terminal.backend_mut().geometric_font = Some(bitmap_fonts.add(font));
+    }
+}
+
+/// Moving to a display with another scale factor changes the glyph sizes, so cells are rebuilt
+/// at the new physical resolution.
+fn handle_scale_factor_change(
+    mut scale_events: EventReader<WindowScaleFactorChanged>,
+    mut statuses: Query<&mut TermStatus>,
+) {
+    if scale_events.read().last().is_some() {
+        for mut status in statuses.iter_mut() {
+            status.rebuild();
+        }
     }
 }
 
 fn init_virtual_cells(
     mut commands: Commands,
-    mut terminal_query: Query<(&Node, Entity, &mut TerminalComponent)>,
-    mut app_state: ResMut<NextState<TermState>>,
+    mut terminal_query: Query<(Entity, &mut TerminalComponent, &mut TermStatus)>,
+    mut stats: Option<ResMut<SyncStats>>,
 ) {
-    let (nodik, e, mut termy) = terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend");
-    let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
-    let rat_term = &mut termy.ratatui_terminal;
+    for (e, mut termy, mut status) in terminal_query.iter_mut() {
+        if status.state != TermState::NeedsIniting {
+            continue;
+        }
+        let ns = termy.get_text_style(BevyColor::DARK_GRAY, FontStyle::Normal);
+        let rat_term = &mut termy.ratatui_terminal;
+
+        let termy_backend = rat_term.backend_mut();
+        termy_backend.cell_columns = termy_backend.width;
 
-    let termy_backend = rat_term.backend_mut();
+        if termy_backend.render_mode == RenderMode::Rows {
+            termy_backend.row_entities = spawn_rows(&mut commands, e, termy_backend);
+        } else {
+            termy_backend.cell_entities = spawn_cells(&mut commands, e, termy_backend, ns);
+        }
+
+        status.set(TermState::Ready);
+        if let Some(stats) = &mut stats {
+            stats.rebuilt.push((e, false));
+        }
+    }
+}
+
+/// Spawns an entity for every cell as children of the terminal.
+fn spawn_cells(
+    commands: &mut Commands,
+    terminal: Entity,
+    termy_backend: &BevyBackend,
+    ns: TextStyle,
+) -> Vec<Entity> {
     let rows = termy_backend.height;
     let columns = termy_backend.width;
-    termy_backend.entity_map = HashMap::new();
-
-    let node_size = nodik.size();
+    let mut cells = Vec::with_capacity(rows as usize * columns as usize);
 
     for y in 0..rows {
         for x in 0..columns {
             let ratcell = termy_backend.buffer.get(x, y);
+            let pos = termy_backend.cell_position(x, y);
             let vcell = commands
                 .spawn((
                     CellComponent::from_cell(ratcell.clone()),
-                    TextBundle::from_section(ratcell.symbol(), ns.clone()).with_style(Style {
-                        top: Val::Px(y as f32 * node_size.y),
-                        left: Val::Px(x as f32 * node_size.x),
-
-                        ..default()
-                    }),
+                    TextBundle::from_section(ratcell.symbol(), ns.clone())
+                        .with_text_justify(JustifyText::Center)
+                        .with_style(Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(pos.y),
+                            left: Val::Px(pos.x),
+                            width: Val::Px(termy_backend.cell_size.x),
+                            height: Val::Px(termy_backend.cell_size.y),
+
+                            ..default()
+                        }),
+                    TerminalPart,
                 ))
                 .id();
 
-            termy_backend.entity_map.insert((x, y), vcell);
+            cells.push(vcell);
         }
     }
-
-    app_state.set(TermState::AllTermsInited);
+    commands.entity(terminal).push_children(&cells);
+    cells
 }
 
+/// Copies the cells drawn since the last frame into their cell components, in place so only the
+/// cells that really changed are picked up by `update_ents_from_comp`.
 fn update_ents_from_vcupdate(
-    mut commands: Commands,
-    mut terminal_query: Query<(&mut TerminalComponent)>,
+    mut terminal_query: Query<(&mut TerminalComponent, &TermStatus)>,
+    mut cell_query: Query<&mut CellComponent>,
+    mut row_query: Query<&mut RowText>,
+    mut errors: EventWriter<TerminalError>,
+    mut stats: Option<ResMut<SyncStats>>,
 ) {
-    let mut termy = &mut terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend")
-        .ratatui_terminal;
-    let termy_backend = termy.backend_mut();
-    let boop = termy_backend.entity_map.clone();
-
-    while let Some((x, y, vc)) = termy_backend.vcupdate.pop() {
-        let xy = (x.clone(), y.clone());
-        match boop.get(&xy) {
-            Some(wow) => {
-                commands.entity(*wow).insert(CellComponent::from_cell(vc));
-                ()
+    let start = stats.is_some().then(Instant::now);
+    let mut cells_changed = 0;
+    for (mut termy, status) in terminal_query.iter_mut() {
+        // what is drawn before the cells are laid out stays queued until they are
+        if !matches!(status.state, TermState::Ready | TermState::NeedsIniting) {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+        let mut updates = std::mem::take(&mut termy_backend.vcupdate);
+
+        if termy_backend.render_mode == RenderMode::Rows {
+            // rows are rebuilt from the buffer, so they only need to know they were drawn into
+            cells_changed += updates.len();
+            for (x, y, _) in updates.drain(..) {
+                let Some((_, text)) = termy_backend.row_entities.get(y as usize) else {
+                    continue;
+                };
+                match row_query.get_mut(*text) {
+                    Ok(mut row) if x < termy_backend.cell_columns => row.set_changed(),
+                    Ok(_) => {}
+                    Err(_) => {
+                        errors.send(TerminalError::new(
+                            "update_ents_from_vcupdate",
+                            TerminalErrorKind::MissingEntity(*text),
+                        ));
+                    }
+                }
             }
-            None => (),
-        };
+            termy_backend.vcupdate = updates;
+            continue;
+        }
+
+        // in draw order, so the last draw of a cell this frame wins
+        for (x, y, vc) in updates.drain(..) {
+            let Some(entity) = termy_backend.cell_entity(x, y) else {
+                continue;
+            };
+            match cell_query.get_mut(entity) {
+                Ok(mut cellii) => {
+                    if cellii.cell != vc {
+                        cellii.cell = vc;
+                        cells_changed += 1;
+                    }
+                }
+                Err(_) => {
+                    errors.send(TerminalError::new(
+                        "update_ents_from_vcupdate",
+                        TerminalErrorKind::MissingEntity(entity),
+                    ));
+                }
+            }
+        }
+        // keeps the allocation for the next frame
+        termy_backend.vcupdate = updates;
+    }
+
+    if let (Some(stats), Some(start)) = (&mut stats, start) {
+        stats.cells_changed += cells_changed;
+        stats.vcupdate_time += start.elapsed();
     }
 }
 
 fn handle_primary_window_resize(
-    mut windows: Query<&mut Window, With<PrimaryWindow>>,
-    mut terminal_query: Query<(&mut TerminalComponent, &Node)>,
+    mut terminal_query: Query<(&mut TerminalComponent, &mut TermStatus)>,
     mut resize_event: EventReader<WindowResized>,
-    mut app_state: ResMut<NextState<TermState>>,
 ) {
-    if let Ok((mut termy, nodik)) = terminal_query.get_single_mut() {
-        for wr in resize_event.read() {
-            let termy_backend = termy.ratatui_terminal.backend_mut();
-
-            if !termy_backend.manual_window_sizing {
-                let node_size = nodik.size();
+    let Some(wr) = resize_event.read().last() else {
+        return;
+    };
+    for (mut termy, mut status) in terminal_query.iter_mut() {
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+
+        if termy_backend.embedded {
+            continue;
+        }
 
-                let w_wid = node_size.x;
-                let w_hei = node_size.y;
+        // the window keeps its size, the clamped grid is centred inside it
+        if termy_backend.fit_to_area(Vec2::new(wr.width, wr.height)) {
+            status.rebuild();
+        }
+    }
+}
 
-                let new_wid = (wr.width / w_wid) as u16;
-                let new_hei = (wr.height / w_hei) as u16;
+/// Spawns a node for every image placed on the terminal, despawns the ones that were overwritten
+/// and keeps the rest lined up with their cells.
+fn update_terminal_images(
+    mut commands: Commands,
+    mut terminal_query: Query<(Entity, &mut TerminalComponent, &TermStatus)>,
+    mut image_nodes: Query<&mut Style, With<UiImage>>,
+) {
+    for (e, mut termy, status) in terminal_query.iter_mut() {
+        if !status.ready() {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend_mut();
 
-                termy_backend.resize(new_wid as u16, new_hei as u16);
-                app_state.set(TermState::TermNeedsClearing);
+        for entity in termy_backend.stale_images.drain(..) {
+            if let Some(entity) = commands.get_entity(entity) {
+                entity.despawn_recursive();
+            }
+        }
 
-                for mut window in windows.iter_mut() {
-                    window.resolution =
-                        WindowResolution::new(new_wid as f32 * w_wid, new_hei as f32 * w_hei);
+        let cell_size = termy_backend.cell_size;
+        let positions: Vec<(Vec2, Vec2)> = termy_backend
+            .images
+            .iter()
+            .map(|image| {
+                let area = image.area;
+                let size = Vec2::new(area.width as f32, area.height as f32) * cell_size;
+                (termy_backend.cell_position(area.x, area.y), size)
+            })
+            .collect();
+
+        for (image, (pos, size)) in termy_backend.images.iter_mut().zip(positions) {
+            let style = Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(pos.y),
+                left: Val::Px(pos.x),
+                width: Val::Px(size.x),
+                height: Val::Px(size.y),
+                ..default()
+            };
 
-                    // Query returns one window typically.
+            if image.entity == Entity::PLACEHOLDER {
+                image.entity = commands
+                    .spawn((
+                        ImageBundle {
+                            style,
+                            image: UiImage::new(image.image.clone()),
+                            // above the cells and their glyph sprites
+                            z_index: ZIndex::Global(1),
+                            ..default()
+                        },
+                        TerminalPart,
+                    ))
+                    .set_parent(e)
+                    .id();
+            } else if let Ok(mut current) = image_nodes.get_mut(image.entity) {
+                if *current != style {
+                    *current = style;
                 }
             }
         }
     }
 }
 
-fn debug_entities(query_cells: Query<(Entity, &Node)>) {
-    for (entity_id, cs) in query_cells.iter() {
+/// Keeps the grid of an embedded terminal matched to the computed layout of its parent node.
+fn handle_parent_node_resize(
+    mut terminal_query: Query<(&mut TerminalComponent, &mut TermStatus, &Parent)>,
+    parent_nodes: Query<&Node, Without<TerminalComponent>>,
+) {
+    for (mut termy, mut status, parent) in terminal_query.iter_mut() {
+        if !status.ready() {
+            continue;
+        }
+        let Ok(parent_node) = parent_nodes.get(parent.get()) else {
+            continue;
+        };
 
-        //   println!("the calculated size is {:?}",cs.size());
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+        if termy_backend.embedded && termy_backend.fit_to_area(parent_node.size()) {
+            status.rebuild();
+        }
     }
 }
 
 fn update_ents_from_comp(
     //this should run after update from vcbuffer
-    query_cells: Query<
-        (
-            Entity,
-            &CellComponent,
-            &Style,
-            Option<&SlowBlink>,
-            Option<&RapidBlink>,
-        ),
-        (Changed<CellComponent>),
-    >,
+    mut query_cells: Query<CellQueryItem, (Changed<CellComponent>, Without<GlyphSprite>)>,
+    mut glyph_query: Query<GlyphQueryItem, With<GlyphSprite>>,
     mut commands: Commands,
-    terminal_query: Query<((&TerminalComponent))>,
+    terminal_query: Query<&TerminalComponent>,
+    bitmap_fonts: Res<Assets<BitmapFont>>,
+    mut stats: Option<ResMut<SyncStats>>,
 ) {
-    let termy = terminal_query
-        .get_single()
-        .expect("More than one terminal with a bevybackend");
-
-    for (entity_id, cellii, stylik, sbo, rbo) in query_cells.iter() {
-       
-            let (proper_fg, proper_bg) = cellii.proper_fg_bg();
-
-            let ns = if (cellii.bold() && cellii.italic()) {
-                termy.get_text_style(proper_fg.clone(), FontStyle::ItalicBold)
-            } else if (cellii.bold()) {
-                termy.get_text_style(proper_fg.clone(), FontStyle::Bold)
-            } else if (cellii.italic()) {
-                termy.get_text_style(proper_fg.clone(), FontStyle::Italic)
-            } else {
-                termy.get_text_style(proper_fg.clone(), FontStyle::Normal)
-            };
+    if query_cells.is_empty() {
+        return;
+    }
+    let start = stats.is_some().then(Instant::now);
+    let mut touched = 0;
+
+    for (entity_id, cellii, parent, mut text, mut background, sbo, rbo, children) in
+        query_cells.iter_mut()
+    {
+        // styled with the fonts of the terminal the cell belongs to
+        let Ok(termy) = terminal_query.get(parent.get()) else {
+            continue;
+        };
+        let (proper_fg, proper_bg) = cellii.proper_fg_bg();
 
-            if cellii.slow_blink() {
-                if sbo.is_none() {
-                    commands.entity(entity_id).insert(SlowBlink {
-                        in_blink: false,
-                        true_color: proper_fg.clone(),
-                    });
-                }
-            } else {
-                commands.entity(entity_id).remove::<SlowBlink>();
+        let font_style = cellii.font_style();
+        let ns = termy.get_text_style(proper_fg, font_style);
+
+        if cellii.slow_blink() {
+            if sbo.is_none() {
+                commands.entity(entity_id).insert(SlowBlink {
+                    in_blink: false,
+                    true_color: proper_fg,
+                });
             }
+        } else if sbo.is_some() {
+            commands.entity(entity_id).remove::<SlowBlink>();
+        }
 
-            if cellii.rapid_blink() {
-                if rbo.is_none() {
-                    commands.entity(entity_id).insert(RapidBlink {
-                        in_blink: false,
-                        true_color: proper_fg.clone(),
-                    });
-                }
-            } else {
-                commands.entity(entity_id).remove::<RapidBlink>();
+        if cellii.rapid_blink() {
+            if rbo.is_none() {
+                commands.entity(entity_id).insert(RapidBlink {
+                    in_blink: false,
+                    true_color: proper_fg,
+                });
             }
+        } else if rbo.is_some() {
+            commands.entity(entity_id).remove::<RapidBlink>();
+        }
 
-            commands.entity(entity_id).insert(
-                TextBundle::from_section(cellii.proper_symbol(), ns)
-                    .with_background_color(proper_bg)
-                    .with_text_justify(JustifyText::Center)
-                    .with_style(stylik.clone()),
-            );
-        
+        // cells drawn from an atlas keep an empty text, so blinking and styling still apply
+        let glyph = termy.atlas_glyph(font_style, &cellii.cell, &bitmap_fonts);
+        let symbol = if glyph.is_some() {
+            String::new()
+        } else {
+            cellii.proper_symbol()
+        };
+        let tint = match &glyph {
+            Some(glyph) if !glyph.tinted && !cellii.hidden() => BevyColor::WHITE,
+            _ => proper_fg,
+        };
+        sync_glyph_sprite(
+            &mut commands,
+            entity_id,
+            children,
+            &mut glyph_query,
+            glyph,
+            tint,
+        );
+
+        // only touched when different, a changed Text is laid out again
+        let unchanged = matches!(
+            text.sections.as_slice(),
+            [section] if section.value == symbol && same_text_style(&section.style, &ns)
+        );
+        if !unchanged {
+            text.sections = vec![TextSection::new(symbol, ns)];
+        }
+        let recolored = background.0 != proper_bg;
+        if recolored {
+            background.0 = proper_bg;
+        }
+        if !unchanged || recolored {
+            touched += 1;
+        }
+    }
+
+    if let (Some(stats), Some(start)) = (&mut stats, start) {
+        stats.entities_touched += touched;
+        stats.comp_time += start.elapsed();
+    }
+}
+
+pub(crate) fn same_text_style(a: &TextStyle, b: &TextStyle) -> bool {
+    a.font == b.font && a.font_size == b.font_size && a.color == b.color
+}
+
+/// Shows the atlas tile a cell is drawn with in a child of the cell, spawning the child the first
+/// time, or hides that child when the cell goes back to text.
+fn sync_glyph_sprite(
+    commands: &mut Commands,
+    cell: Entity,
+    children: Option<&Children>,
+    glyph_query: &mut Query<GlyphQueryItem, With<GlyphSprite>>,
+    glyph: Option<AtlasGlyph>,
+    tint: BevyColor,
+) {
+    let existing = children
+        .into_iter()
+        .flatten()
+        .find(|child| glyph_query.contains(**child))
+        .copied();
+
+    match (glyph, existing.and_then(|e| glyph_query.get_mut(e).ok())) {
+        (Some(glyph), Some((mut atlas, mut image, mut sprite_tint, mut visibility))) => {
+            atlas.layout = glyph.layout;
+            atlas.index = glyph.index;
+            if image.texture != glyph.image {
+                image.texture = glyph.image;
+            }
+            sprite_tint.0 = tint;
+            *visibility = Visibility::Inherited;
+        }
+        (Some(glyph), None) => {
+            commands.entity(cell).with_children(|parent| {
+                parent.spawn((
+                    GlyphSprite,
+                    AtlasImageBundle {
+                        style: Style {
+                            position_type: PositionType::Absolute,
+                            top: Val::Px(0.0),
+                            left: Val::Px(0.0),
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        background_color: tint.into(),
+                        image: UiImage::new(glyph.image),
+                        texture_atlas: TextureAtlas {
+                            layout: glyph.layout,
+                            index: glyph.index,
+                        },
+                        ..default()
+                    },
+                ));
+            });
+        }
+        (None, Some((_, _, _, mut visibility))) => {
+            *visibility = Visibility::Hidden;
+        }
+        (None, None) => {}
+    }
+}
+
+/// A bitmap font that finishes loading after the cells were laid out changes the cell size, so
+/// the cells are rebuilt from it.
+fn handle_bitmap_font_loaded(
+    mut font_events: EventReader<AssetEvent<BitmapFont>>,
+    mut terminal_query: Query<(&TerminalComponent, &mut TermStatus)>,
+) {
+    for event in font_events.read() {
+        let AssetEvent::LoadedWithDependencies { id } = event else {
+            continue;
+        };
+        for (termy, mut status) in terminal_query.iter_mut() {
+            let used = [
+                FontStyle::Normal,
+                FontStyle::Bold,
+                FontStyle::Italic,
+                FontStyle::ItalicBold,
+            ]
+            .into_iter()
+            .any(|style| termy.get_bitmap_font(style).map(|h| h.id()) == Some(*id));
+            if used && status.ready() {
+                status.rebuild();
+            }
+        }
     }
 }
 
 fn font_setup(
     asset_server: Res<AssetServer>,
-    mut terminal_query: Query<((Entity, &mut TerminalComponent))>,
-    mut app_state: ResMut<NextState<TermState>>,
+    mut terminal_query: Query<(&mut TerminalComponent, &mut TermStatus)>,
 ) {
-    let (e, mut termy) = terminal_query
-        .get_single_mut()
-        .expect("More than one terminal with a bevybackend");
-    let mut termy_backend = termy.ratatui_terminal.backend_mut();
+    for (mut termy, mut status) in terminal_query.iter_mut() {
+        if status.state != TermState::NeedsFont {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend_mut();
 
-    if let Some(x) = &termy_backend.normal_font_path {
-        termy_backend.normal_handle = asset_server.load(x);
-    }
-    if let Some(x) = &termy_backend.italic_font_path {
-        termy_backend.italic_handle = asset_server.load(x);
-    }
-    if let Some(x) = &termy_backend.bold_font_path {
-        termy_backend.bold_handle = asset_server.load(x);
-    }
-    if let Some(x) = &termy_backend.italicbold_font_path {
-        termy_backend.italicbold_handle = asset_server.load(x);
-    }
+        // bitmap fonts go in their own handles, the TTF handles stay on the default font
+        if let Some(x) = &termy_backend.normal_font_path {
+            if is_bitmap_font_path(x) {
+                termy_backend.normal_bitmap = Some(asset_server.load(x));
+            } else {
+                termy_backend.normal_handle = asset_server.load(x);
+            }
+        }
+        if let Some(x) = &termy_backend.italic_font_path {
+            if is_bitmap_font_path(x) {
+                termy_backend.italic_bitmap = Some(asset_server.load(x));
+            } else {
+                termy_backend.italic_handle = asset_server.load(x);
+            }
+        }
+        if let Some(x) = &termy_backend.bold_font_path {
+            if is_bitmap_font_path(x) {
+                termy_backend.bold_bitmap = Some(asset_server.load(x));
+            } else {
+                termy_backend.bold_handle = asset_server.load(x);
+            }
+        }
+        if let Some(x) = &termy_backend.italicbold_font_path {
+            if is_bitmap_font_path(x) {
+                termy_backend.italicbold_bitmap = Some(asset_server.load(x));
+            } else {
+                termy_backend.italicbold_handle = asset_server.load(x);
+            }
+        }
 
-    app_state.set(TermState::TermNeedsClearing);
+        status.set(TermState::NeedsClearing);
+    }
 }
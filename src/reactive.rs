@@ -0,0 +1,204 @@
+//! Drawing only when something changed, for tool style apps that sit idle most of the time.
+//! Insert [`ReactiveRendering`] along with `WinitSettings::desktop_app()` and the app only runs a
+//! frame when there is input, or when the plugin sends a `RequestRedraw` because a terminal was
+//! drawn to, is being rebuilt, or has blinking cells. Send `RequestRedraw` yourself to wake it
+//! for your own timers.
+//!
+//! Pty, threaded app and server terminals are only polled on frames, give `WinitSettings` a
+//! short enough `wait` for them.
+
+use bevy::{
+    prelude::*,
+    utils::Duration,
+    window::RequestRedraw,
+    winit::{UpdateMode, WinitSettings},
+};
+
+use crate::{
+    components::{CellComponent, RapidBlink, SlowBlink, TerminalComponent},
+    ratatui_plugin::{TermStatus, RAPID_BLINK, SLOW_BLINK},
+    row_text::RowText,
+};
+
+/// Makes the plugin wake a reactive event loop when a terminal needs another frame, see the
+/// module docs.
+#[derive(Resource, Debug, Clone)]
+pub struct ReactiveRendering {
+    /// Keep blinking cells going by waking up on the blink timers, otherwise they freeze until
+    /// something else wakes the app
+    pub blink: bool,
+    /// The focused and unfocused modes of `WinitSettings` while the plugin has their wait
+    /// shortened for blinking
+    focused: Option<Shortened>,
+    unfocused: Option<Shortened>,
+}
+
+impl Default for ReactiveRendering {
+    fn default() -> Self {
+        ReactiveRendering {
+            blink: true,
+            focused: None,
+            unfocused: None,
+        }
+    }
+}
+
+/// An update mode with its wait shortened, and the mode it was configured as before.
+#[derive(Debug, Clone, Copy)]
+struct Shortened {
+    configured: UpdateMode,
+    written: UpdateMode,
+}
+
+impl ReactiveRendering {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn blink(mut self, blink: bool) -> Self {
+        self.blink = blink;
+        self
+    }
+}
+
+/// True when any terminal was drawn to since its cells were last synced.
+pub(crate) fn terminals_drawn(terminal_query: Query<&TerminalComponent>) -> bool {
+    terminal_query
+        .iter()
+        .any(|termy| !termy.ratatui_terminal.backend().vcupdate.is_empty())
+}
+
+/// Asks for another frame when the cells synced this frame still have to be turned into text, or
+/// a terminal is part way through being set up or rebuilt.
+pub(crate) fn request_redraws(
+    changed_cells: Query<(), Changed<CellComponent>>,
+    changed_rows: Query<(), Changed<RowText>>,
+    terminal_query: Query<Option<&TermStatus>, With<TerminalComponent>>,
+    mut redraw: EventWriter<RequestRedraw>,
+) {
+    // a terminal without a status was spawned this frame and is set up on the next one
+    let rebuilding = terminal_query
+        .iter()
+        .any(|status| status.is_none_or(|status| status.next.is_some() || !status.ready()));
+    if rebuilding || !changed_cells.is_empty() || !changed_rows.is_empty() {
+        redraw.send(RequestRedraw);
+    }
+}
+
+/// Shortens the wait of a reactive event loop to the blink interval while cells blink, and puts
+/// it back once they stop. The kind of update mode is left as configured, and a mode changed in
+/// the meantime is kept as the new configured one.
+pub(crate) fn wake_for_blink(
+    mut reactive: ResMut<ReactiveRendering>,
+    winit: Option<ResMut<WinitSettings>>,
+    slow: Query<(), With<SlowBlink>>,
+    rapid: Query<(), With<RapidBlink>>,
+) {
+    let Some(mut winit) = winit else {
+        return;
+    };
+    let wait = if !reactive.blink {
+        None
+    } else if !rapid.is_empty() {
+        Some(RAPID_BLINK)
+    } else if !slow.is_empty() {
+        Some(SLOW_BLINK)
+    } else {
+        None
+    };
+
+    let reactive = &mut *reactive;
+    // only written when different, a changed WinitSettings is looked at again by winit
+    let (mut focused, mut unfocused) = (winit.focused_mode, winit.unfocused_mode);
+    shorten_wait(&mut focused, &mut reactive.focused, wait);
+    shorten_wait(&mut unfocused, &mut reactive.unfocused, wait);
+    if !same_mode(&focused, &winit.focused_mode) || !same_mode(&unfocused, &winit.unfocused_mode) {
+        winit.focused_mode = focused;
+        winit.unfocused_mode = unfocused;
+    }
+}
+
+/// Caps the wait of `mode` at `wait`, or puts back the configured mode when there is no wait.
+fn shorten_wait(mode: &mut UpdateMode, shortened: &mut Option<Shortened>, wait: Option<Duration>) {
+    // someone else set the mode since it was shortened, that is the configured mode now
+    if shortened.is_some_and(|s| !same_mode(mode, &s.written)) {
+        *shortened = None;
+    }
+    let configured = shortened.map_or(*mode, |s| s.configured);
+    let wanted = match (configured, wait) {
+        (UpdateMode::Reactive { wait: current }, Some(wait)) => UpdateMode::Reactive {
+            wait: current.min(wait),
+        },
+        (UpdateMode::ReactiveLowPower { wait: current }, Some(wait)) => {
+            UpdateMode::ReactiveLowPower {
+                wait: current.min(wait),
+            }
+        }
+        (configured, _) => configured,
+    };
+    *mode = wanted;
+    *shortened = (!same_mode(&wanted, &configured)).then_some(Shortened {
+        configured,
+        written: wanted,
+    });
+}
+
+fn same_mode(a: &UpdateMode, b: &UpdateMode) -> bool {
+    match (a, b) {
+        (UpdateMode::Continuous, UpdateMode::Continuous) => true,
+        (UpdateMode::Reactive { wait: a }, UpdateMode::Reactive { wait: b }) => a == b,
+        (UpdateMode::ReactiveLowPower { wait: a }, UpdateMode::ReactiveLowPower { wait: b }) => {
+            a == b
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modes(app: &App) -> (UpdateMode, UpdateMode) {
+        let winit = app.world.resource::<WinitSettings>();
+        (winit.focused_mode, winit.unfocused_mode)
+    }
+
+    #[test]
+    fn blinking_shortens_the_wait_and_keeps_changed_modes() {
+        let mut app = App::new();
+        app.insert_resource(ReactiveRendering::default())
+            .insert_resource(WinitSettings::desktop_app())
+            .add_systems(Update, wake_for_blink);
+        let blink = app
+            .world
+            .spawn(SlowBlink {
+                in_blink: false,
+                true_color: Color::WHITE,
+            })
+            .id();
+
+        app.update();
+        let (focused, unfocused) = modes(&app);
+        assert!(same_mode(
+            &focused,
+            &UpdateMode::Reactive { wait: SLOW_BLINK }
+        ));
+        assert!(same_mode(
+            &unfocused,
+            &UpdateMode::ReactiveLowPower { wait: SLOW_BLINK }
+        ));
+
+        // changed while blinking, it stays what it was changed to
+        app.world.resource_mut::<WinitSettings>().focused_mode = UpdateMode::Continuous;
+        app.update();
+        app.world.despawn(blink);
+        app.update();
+
+        let (focused, unfocused) = modes(&app);
+        assert!(same_mode(&focused, &UpdateMode::Continuous));
+        assert!(same_mode(
+            &unfocused,
+            &WinitSettings::desktop_app().unfocused_mode
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+//! Records what a terminal shows over time as an asciinema v2 cast. Every draw batch becomes an
+//! output event of cursor moves, SGR codes and symbols, so the cast replays in `asciinema play`
+//! or the web player exactly as the frames were drawn.
+
+use std::{
+    fs, io,
+    path::Path,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use ratatui::buffer::{Buffer, Cell};
+use serde::Serialize;
+
+use crate::export::{ansi_batch, screen_cells};
+
+/// First line of a cast.
+#[derive(Serialize)]
+struct CastHeader {
+    version: u8,
+    width: u16,
+    height: u16,
+    timestamp: u64,
+}
+
+/// A recording in progress, started with [`crate::BevyBackend::start_recording`].
+#[derive(Debug, Clone)]
+pub struct CastRecorder {
+    pub width: u16,
+    pub height: u16,
+    /// Unix time the recording started at
+    pub timestamp: u64,
+    start: Instant,
+    /// (seconds since start, event code, data)
+    events: Vec<(f64, &'static str, String)>,
+}
+
+impl CastRecorder {
+    /// Starts a recording with the current screen as its first frame.
+    pub fn new(buffer: &Buffer) -> Self {
+        let mut recorder = CastRecorder {
+            width: buffer.area.width,
+            height: buffer.area.height,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            start: Instant::now(),
+            events: Vec::new(),
+        };
+        recorder.output("\x1b[H\x1b[2J".to_string());
+        recorder.record_draw(&screen_cells(buffer));
+        recorder
+    }
+
+    fn push(&mut self, code: &'static str, data: String) {
+        let time = self.start.elapsed().as_secs_f64();
+        self.events.push((time, code, data));
+    }
+
+    /// Records raw output, escape codes and all.
+    pub fn output(&mut self, data: String) {
+        self.push("o", data);
+    }
+
+    /// Records a batch of drawn cells.
+    pub fn record_draw(&mut self, cells: &[(u16, u16, Cell)]) {
+        if !cells.is_empty() {
+            self.output(ansi_batch(cells));
+        }
+    }
+
+    /// Records the terminal changing size.
+    pub fn record_resize(&mut self, width: u16, height: u16) {
+        self.push("r", format!("{}x{}", width, height));
+    }
+
+    /// The recording in asciinema v2 format, a JSON header line followed by one line per event.
+    pub fn to_cast(&self) -> String {
+        let header = CastHeader {
+            version: 2,
+            width: self.width,
+            height: self.height,
+            timestamp: self.timestamp,
+        };
+        let mut cast = String::new();
+        push_line(&mut cast, &header);
+        for (time, code, data) in &self.events {
+            // microseconds are plenty and keep the lines short
+            let time = (time * 1e6).round() / 1e6;
+            push_line(&mut cast, &(time, code, data));
+        }
+        cast
+    }
+
+    /// Writes the recording to a `.cast` file.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.to_cast())
+    }
+}
+
+/// Appends a value as one line of JSON. Headers and events are numbers and strings, which always
+/// serialize.
+fn push_line(cast: &mut String, value: &impl Serialize) {
+    if let Ok(line) = serde_json::to_string(value) {
+        cast.push_str(&line);
+        cast.push('\n');
+    }
+}
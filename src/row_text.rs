@@ -0,0 +1,188 @@
+//! [`RenderMode::Rows`](crate::RenderMode::Rows): every row of the grid is one text entity with a
+//! section per run of cells in the same colour and font, drawn over a node holding a background
+//! quad per run of the same background. The glyphs of a row are laid out by the font, so this
+//! needs a monospace font, and the cell width follows its advance so the cursor, images and
+//! backgrounds stay lined up with the text.
+
+use bevy::{prelude::*, utils::Instant};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    components::{CellComponent, TerminalComponent},
+    diagnostics::SyncStats,
+    ratatui_plugin::{same_text_style, TerminalPart},
+    BevyBackend,
+};
+
+/// The text entity of a row, marked changed when a cell of the row is drawn.
+#[derive(Component)]
+pub(crate) struct RowText {
+    pub(crate) y: u16,
+}
+
+/// A background quad, child of the background node of its row.
+#[derive(Component)]
+pub(crate) struct RunBackground;
+
+/// Spawns the background node and text entity of every row as children of the terminal,
+/// backgrounds first so no row's background covers the text of the row above.
+pub(crate) fn spawn_rows(
+    commands: &mut Commands,
+    terminal: Entity,
+    termy_backend: &BevyBackend,
+) -> Vec<(Entity, Entity)> {
+    let width = termy_backend.width as f32 * termy_backend.cell_size.x;
+    let height = termy_backend.cell_size.y;
+
+    let mut backgrounds = Vec::with_capacity(termy_backend.height as usize);
+    let mut texts = Vec::with_capacity(termy_backend.height as usize);
+    for y in 0..termy_backend.height {
+        let pos = termy_backend.cell_position(0, y);
+        let background = commands
+            .spawn((
+                NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(pos.y),
+                        left: Val::Px(pos.x),
+                        width: Val::Px(width),
+                        height: Val::Px(height),
+                        ..default()
+                    },
+                    ..default()
+                },
+                TerminalPart,
+            ))
+            .id();
+        let text = commands
+            .spawn((
+                TextBundle::from_sections([])
+                    .with_no_wrap()
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        top: Val::Px(pos.y),
+                        left: Val::Px(pos.x),
+                        ..default()
+                    }),
+                RowText { y },
+                TerminalPart,
+            ))
+            .id();
+        backgrounds.push(background);
+        texts.push(text);
+    }
+    commands
+        .entity(terminal)
+        .push_children(&backgrounds)
+        .push_children(&texts);
+
+    backgrounds.into_iter().zip(texts).collect()
+}
+
+/// Rebuilds the text sections and background quads of the rows that were drawn into.
+pub(crate) fn update_rows(
+    mut commands: Commands,
+    mut rows: Query<(&RowText, &Parent, &mut Text), Changed<RowText>>,
+    backgrounds: Query<&Children, Without<RowText>>,
+    mut quads: Query<(&mut Style, &mut BackgroundColor), With<RunBackground>>,
+    terminal_query: Query<&TerminalComponent>,
+    mut stats: Option<ResMut<SyncStats>>,
+) {
+    let start = stats.is_some().then(Instant::now);
+    let mut touched = 0;
+    for (row, parent, mut text) in rows.iter_mut() {
+        let Ok(termy) = terminal_query.get(parent.get()) else {
+            continue;
+        };
+        let termy_backend = termy.ratatui_terminal.backend();
+        if row.y >= termy_backend.height {
+            continue;
+        }
+        let Some((background, _)) = termy_backend.row_entities.get(row.y as usize) else {
+            continue;
+        };
+        let columns = termy_backend.cell_columns.min(termy_backend.width);
+
+        let mut sections: Vec<TextSection> = Vec::new();
+        // first column, columns covered and colour of each background run
+        let mut runs: Vec<(u16, u16, Color)> = Vec::new();
+        let mut x = 0;
+        while x < columns {
+            let cellii = CellComponent::from_cell(termy_backend.buffer.get(x, row.y).clone());
+            // a wide glyph covers the cells after it, those are left out of the text
+            let covered = (cellii.cell.symbol().width() as u16).clamp(1, columns - x);
+            let (proper_fg, proper_bg) = cellii.proper_fg_bg();
+            let style = termy.get_text_style(proper_fg, cellii.font_style());
+
+            match sections.last_mut() {
+                Some(section) if same_text_style(&section.style, &style) => {
+                    section.value.push_str(&cellii.proper_symbol());
+                }
+                _ => sections.push(TextSection::new(cellii.proper_symbol(), style)),
+            }
+            match runs.last_mut() {
+                Some((_, len, color)) if *color == proper_bg => *len += covered,
+                _ => runs.push((x, covered, proper_bg)),
+            }
+            x += covered;
+        }
+        text.sections = sections;
+        // the row and every quad it has now
+        touched += 1 + runs.len();
+
+        let mut existing: Vec<Entity> = backgrounds
+            .get(*background)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|child| quads.contains(**child))
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default();
+        // quads left over from when the row had more runs
+        if existing.len() > runs.len() {
+            for quad in existing.drain(runs.len()..) {
+                commands.entity(quad).despawn_recursive();
+            }
+        }
+        let cell_width = termy_backend.cell_size.x;
+        for (i, (start, len, color)) in runs.iter().enumerate() {
+            let style = Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                left: Val::Px(*start as f32 * cell_width),
+                width: Val::Px(*len as f32 * cell_width),
+                height: Val::Percent(100.0),
+                ..default()
+            };
+            match existing.get(i).and_then(|quad| quads.get_mut(*quad).ok()) {
+                Some((mut quad_style, mut quad_color)) => {
+                    if *quad_style != style {
+                        *quad_style = style;
+                    }
+                    if quad_color.0 != *color {
+                        quad_color.0 = *color;
+                    }
+                }
+                None => {
+                    commands
+                        .spawn((
+                            NodeBundle {
+                                style,
+                                background_color: (*color).into(),
+                                ..default()
+                            },
+                            RunBackground,
+                        ))
+                        .set_parent(*background);
+                }
+            }
+        }
+    }
+
+    if let (Some(stats), Some(start)) = (&mut stats, start) {
+        stats.entities_touched += touched;
+        stats.comp_time += start.elapsed();
+    }
+}
@@ -0,0 +1,400 @@
+//! Draws a terminal into an RGBA image on the CPU, so screenshots work on headless machines
+//! without the render pipeline. The cells are drawn the same way the plugin lays them out: the
+//! physical cell size, the Bevy palette, TTF or bitmap fonts, procedural box drawing, tilesets and
+//! placed images.
+
+use std::{error::Error, fmt, path::Path};
+
+use ab_glyph::{Font as _, PxScale, ScaleFont as _};
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::{Color as BevyColor, *},
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+use ratatui::layout::Rect as RatRect;
+
+use crate::{
+    box_drawing::{draw_geometric_char, is_geometric_char},
+    components::{CellComponent, TerminalComponent},
+    image_widget::image_pixel,
+    BitmapFont, FontStyle,
+};
+
+/// Errors that can happen while saving a screenshot.
+#[derive(Debug)]
+pub enum ScreenshotError {
+    /// The cell size is unknown, the font hasn't loaded and layout hasn't run
+    NoCellSize,
+    Image(image::ImageError),
+}
+
+impl fmt::Display for ScreenshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScreenshotError::NoCellSize => write!(f, "terminal cell size is not known yet"),
+            ScreenshotError::Image(e) => write!(f, "could not write screenshot: {}", e),
+        }
+    }
+}
+
+impl Error for ScreenshotError {}
+
+impl From<image::ImageError> for ScreenshotError {
+    fn from(e: image::ImageError) -> Self {
+        ScreenshotError::Image(e)
+    }
+}
+
+/// The assets a terminal is drawn from, use it in a system to take screenshots.
+#[derive(SystemParam)]
+pub struct TerminalRasterizer<'w> {
+    fonts: Res<'w, Assets<Font>>,
+    bitmap_fonts: Res<'w, Assets<BitmapFont>>,
+    images: Res<'w, Assets<Image>>,
+    layouts: Res<'w, Assets<TextureAtlasLayout>>,
+}
+
+/// RGBA pixels being drawn into.
+struct Canvas {
+    size: UVec2,
+    data: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(size: UVec2) -> Self {
+        Canvas {
+            size,
+            data: vec![0; (size.x * size.y * 4) as usize],
+        }
+    }
+
+    /// Blends a colour over a pixel, alpha is multiplied by coverage.
+    fn blend(&mut self, x: i32, y: i32, color: [u8; 4], coverage: f32) {
+        if x < 0 || y < 0 || x >= self.size.x as i32 || y >= self.size.y as i32 {
+            return;
+        }
+        let alpha = (color[3] as f32 / 255.0 * coverage).clamp(0.0, 1.0);
+        let offset = ((y as u32 * self.size.x + x as u32) * 4) as usize;
+        let px = &mut self.data[offset..offset + 4];
+        for i in 0..3 {
+            px[i] = (px[i] as f32 * (1.0 - alpha) + color[i] as f32 * alpha).round() as u8;
+        }
+        px[3] = px[3].max((alpha * 255.0) as u8);
+    }
+
+    fn fill(&mut self, min: IVec2, size: UVec2, color: [u8; 4]) {
+        for y in 0..size.y as i32 {
+            for x in 0..size.x as i32 {
+                self.blend(min.x + x, min.y + y, color, 1.0);
+            }
+        }
+    }
+
+    /// Draws a coverage bitmap scaled to `size` with nearest neighbour sampling.
+    fn coverage(&mut self, min: IVec2, size: UVec2, glyph: (&[u8], UVec2), color: [u8; 4]) {
+        let (coverage, glyph_size) = glyph;
+        if glyph_size.x == 0 || glyph_size.y == 0 {
+            return;
+        }
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let gx = x * glyph_size.x / size.x;
+                let gy = y * glyph_size.y / size.y;
+                let alpha = coverage
+                    .get((gy * glyph_size.x + gx) as usize)
+                    .copied()
+                    .unwrap_or(0);
+                if alpha > 0 {
+                    self.blend(
+                        min.x + x as i32,
+                        min.y + y as i32,
+                        color,
+                        alpha as f32 / 255.0,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Draws part of an image scaled to `size`, multiplied by the tint.
+    fn image(&mut self, min: IVec2, size: UVec2, source: (&Image, Rect), tint: [u8; 4]) {
+        let (image, rect) = source;
+        if size.x == 0 || size.y == 0 {
+            return;
+        }
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let sx = rect.min.x + (x as f32 + 0.5) / size.x as f32 * rect.width();
+                let sy = rect.min.y + (y as f32 + 0.5) / size.y as f32 * rect.height();
+                let Some(px) = image_pixel(image, sx as u32, sy as u32) else {
+                    continue;
+                };
+                let tinted = [0, 1, 2, 3].map(|i| (px[i] as u32 * tint[i] as u32 / 255) as u8);
+                self.blend(min.x + x as i32, min.y + y as i32, tinted, 1.0);
+            }
+        }
+    }
+
+    fn into_image(self) -> Image {
+        Image::new(
+            Extent3d {
+                width: self.size.x,
+                height: self.size.y,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            self.data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::all(),
+        )
+    }
+}
+
+fn rgba(color: BevyColor) -> [u8; 4] {
+    color.as_rgba_u8()
+}
+
+impl TerminalRasterizer<'_> {
+    /// Size of one cell in physical pixels, measured by layout or worked out from the font when
+    /// layout hasn't run.
+    fn cell_size(&self, termy: &TerminalComponent) -> Option<UVec2> {
+        let termy_backend = termy.ratatui_terminal.backend();
+        let measured = termy_backend.physical_cell_size();
+        if measured.x > 0 && measured.y > 0 {
+            return Some(measured);
+        }
+
+        let scale = termy_backend.font_scale * termy_backend.scale_factor;
+        if let Some(bitmap) = termy
+            .get_bitmap_font(FontStyle::Normal)
+            .and_then(|handle| self.bitmap_fonts.get(handle))
+        {
+            return Some((bitmap.glyph_size.as_vec2() * scale).round().as_uvec2());
+        }
+
+        let style = termy.get_text_style(BevyColor::WHITE, FontStyle::Normal);
+        let font = &self.fonts.get(&style.font)?.font;
+        let scaled = font.as_scaled(PxScale::from(style.font_size * termy_backend.scale_factor));
+        let size = Vec2::new(scaled.h_advance(font.glyph_id('T')), scaled.height());
+        Some(size.round().max(Vec2::ONE).as_uvec2())
+    }
+
+    /// Draws the whole grid of the terminal into a new image at physical resolution.
+    pub fn rasterize(&self, termy: &TerminalComponent) -> Result<Image, ScreenshotError> {
+        let cell = self.cell_size(termy).ok_or(ScreenshotError::NoCellSize)?;
+        let termy_backend = termy.ratatui_terminal.backend();
+        let buffer = &termy_backend.buffer;
+        let mut canvas =
+            Canvas::new(cell * UVec2::new(buffer.area.width as u32, buffer.area.height as u32));
+        let origin =
+            |x: u16, y: u16| IVec2::new(x as i32 * cell.x as i32, y as i32 * cell.y as i32);
+
+        for y in 0..buffer.area.height {
+            for x in 0..buffer.area.width {
+                let cellii = CellComponent::from_cell(buffer.get(x, y).clone());
+                let (fg, bg) = cellii.proper_fg_bg();
+                let min = origin(x, y);
+                canvas.fill(min, cell, rgba(bg));
+                self.draw_symbol(&mut canvas, termy, &cellii, min, cell, fg);
+
+                let line = (cell.y / 12).max(1);
+                if cellii.underlined() {
+                    let at = min + IVec2::new(0, (cell.y - line) as i32);
+                    canvas.fill(at, UVec2::new(cell.x, line), rgba(fg));
+                }
+                if cellii.crossed_out() {
+                    let at = min + IVec2::new(0, (cell.y / 2) as i32);
+                    canvas.fill(at, UVec2::new(cell.x, line), rgba(fg));
+                }
+            }
+        }
+
+        for placed in &termy_backend.images {
+            let Some(image) = self.images.get(&placed.image) else {
+                continue;
+            };
+            let area =
+                placed
+                    .area
+                    .intersection(RatRect::new(0, 0, buffer.area.width, buffer.area.height));
+            let size = cell * UVec2::new(area.width as u32, area.height as u32);
+            let full = Rect::from_corners(Vec2::ZERO, image.size_f32());
+            canvas.image(origin(area.x, area.y), size, (image, full), [255; 4]);
+        }
+
+        Ok(canvas.into_image())
+    }
+
+    /// Draws the symbol of a cell the same way the plugin would pick for it.
+    fn draw_symbol(
+        &self,
+        canvas: &mut Canvas,
+        termy: &TerminalComponent,
+        cellii: &CellComponent,
+        min: IVec2,
+        cell: UVec2,
+        fg: BevyColor,
+    ) {
+        let termy_backend = termy.ratatui_terminal.backend();
+        let symbol = cellii.cell.symbol();
+        let Some(c) = symbol.chars().next() else {
+            return;
+        };
+
+        if let Some(tileset) = &termy_backend.tileset {
+            if let Some(index) = tileset.tile(symbol, cellii.cell.fg) {
+                let image = self.images.get(&tileset.image);
+                let rect = self
+                    .layouts
+                    .get(&tileset.layout)
+                    .and_then(|layout| layout.textures.get(index));
+                if let (Some(image), Some(rect)) = (image, rect) {
+                    let tint = if tileset.tinted || cellii.hidden() {
+                        rgba(fg)
+                    } else {
+                        [255; 4]
+                    };
+                    canvas.image(min, cell, (image, *rect), tint);
+                }
+                return;
+            }
+        }
+
+        if termy_backend.geometric_glyphs && is_geometric_char(c) {
+            canvas.coverage(min, cell, (&draw_geometric_char(c, cell), cell), rgba(fg));
+            return;
+        }
+
+        let font_style = cellii.font_style();
+        if let Some(bitmap) = termy
+            .get_bitmap_font(font_style)
+            .and_then(|handle| self.bitmap_fonts.get(handle))
+        {
+            if let Some(coverage) = bitmap.glyph(symbol).and_then(|i| bitmap.coverage.get(i)) {
+                canvas.coverage(min, cell, (coverage, bitmap.glyph_size), rgba(fg));
+            }
+            return;
+        }
+
+        let style = termy.get_text_style(fg, font_style);
+        let Some(font) = self.fonts.get(&style.font).map(|font| &font.font) else {
+            return;
+        };
+        let scaled = font.as_scaled(PxScale::from(style.font_size * termy_backend.scale_factor));
+        let id = font.glyph_id(c);
+        // centred in the cell like the justified text of the cell nodes
+        let left = (cell.x as f32 - scaled.h_advance(id)) / 2.0;
+        let glyph = id.with_scale_and_position(
+            scaled.scale(),
+            ab_glyph::point(min.x as f32 + left, min.y as f32 + scaled.ascent()),
+        );
+        if let Some(outline) = font.outline_glyph(glyph) {
+            let bounds = outline.px_bounds();
+            let color = rgba(fg);
+            outline.draw(|gx, gy, coverage| {
+                canvas.blend(
+                    bounds.min.x as i32 + gx as i32,
+                    bounds.min.y as i32 + gy as i32,
+                    color,
+                    coverage,
+                );
+            });
+        }
+    }
+
+    /// Rasterizes the terminal and writes it to a PNG file.
+    pub fn save_png(
+        &self,
+        termy: &TerminalComponent,
+        path: impl AsRef<Path>,
+    ) -> Result<(), ScreenshotError> {
+        let image = self.rasterize(termy)?;
+        let size = image.texture_descriptor.size;
+        image::save_buffer_with_format(
+            path,
+            &image.data,
+            size.width,
+            size.height,
+            image::ColorType::Rgba8,
+            image::ImageFormat::Png,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{ecs::system::SystemState, utils::HashMap};
+    use ratatui::{style::Color, Terminal};
+
+    use super::*;
+    use crate::BevyBackend;
+
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const GREEN: [u8; 4] = [0, 255, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    fn pixel(image: &Image, x: u32, y: u32) -> [u8; 4] {
+        image_pixel(image, x, y).unwrap()
+    }
+
+    #[test]
+    fn rasterizes_geometric_and_bitmap_glyphs_at_the_cell_size() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Font>>();
+        world.init_resource::<Assets<Image>>();
+        world.init_resource::<Assets<TextureAtlasLayout>>();
+        // a 2x2 font with a diagonal A
+        let font = BitmapFont {
+            glyph_size: UVec2::new(2, 2),
+            glyphs: HashMap::from_iter([('A', 0)]),
+            coverage: vec![vec![255, 0, 0, 255]],
+            image: Handle::default(),
+            layout: Handle::default(),
+        };
+        let mut bitmap_fonts = Assets::<BitmapFont>::default();
+        let font = bitmap_fonts.add(font);
+        world.insert_resource(bitmap_fonts);
+
+        let mut backend = BevyBackend::default();
+        backend.resize(3, 1);
+        backend.geometric_glyphs(true);
+        backend.normal_bitmap = Some(font);
+        backend.cell_size = Vec2::new(4.0, 8.0);
+        for (x, symbol, fg, bg) in [
+            (0, "█", Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255)),
+            (1, "▌", Color::Rgb(255, 0, 0), Color::Rgb(0, 0, 255)),
+            (2, "A", Color::Rgb(0, 255, 0), Color::Rgb(0, 0, 0)),
+        ] {
+            backend
+                .buffer
+                .get_mut(x, 0)
+                .set_symbol(symbol)
+                .set_fg(fg)
+                .set_bg(bg);
+        }
+        let termy = TerminalComponent {
+            ratatui_terminal: Terminal::new(backend).unwrap(),
+        };
+
+        let mut state = SystemState::<TerminalRasterizer>::new(&mut world);
+        let image = state.get(&world).rasterize(&termy).unwrap();
+
+        assert_eq!(image.size(), UVec2::new(12, 8));
+        // the full block covers its whole cell
+        assert_eq!(pixel(&image, 0, 0), RED);
+        assert_eq!(pixel(&image, 3, 7), RED);
+        // the left half block leaves the background on the right
+        assert_eq!(pixel(&image, 5, 4), RED);
+        assert_eq!(pixel(&image, 6, 4), BLUE);
+        // the bitmap glyph is scaled up to the cell
+        assert_eq!(pixel(&image, 8, 0), GREEN);
+        assert_eq!(pixel(&image, 11, 0), BLACK);
+        assert_eq!(pixel(&image, 8, 7), BLACK);
+        assert_eq!(pixel(&image, 11, 7), GREEN);
+    }
+}
@@ -0,0 +1,291 @@
+//! Serves a terminal to remote viewers over TCP. Every client gets the whole screen when it
+//! connects and then the cells of each draw as ANSI, and what it types comes back as
+//! [`RemoteInput`] events. Works with telnet, or with raw ANSI clients like netcat when telnet
+//! negotiation is turned off.
+
+use std::{
+    io::{self, ErrorKind, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+};
+
+use bevy::prelude::*;
+
+use crate::{
+    components::TerminalComponent,
+    export::{ansi_batch, screen_cells},
+    ratatui_plugin::TermStatus,
+    term_input::{InputDecoder, RemoteInput, TermKeyEvent},
+};
+
+/// Output waiting for a slow client before it gets dropped.
+const MAX_BACKLOG: usize = 4 * 1024 * 1024;
+
+/// IAC WILL ECHO, IAC WILL SUPPRESS-GO-AHEAD: the server echoes and the client sends keys as
+/// they are typed instead of line by line.
+const TELNET_CHARACTER_MODE: &[u8] = &[255, 251, 1, 255, 251, 3];
+
+#[derive(Debug)]
+struct RemoteClient {
+    id: u64,
+    stream: TcpStream,
+    backlog: Vec<u8>,
+    decoder: InputDecoder,
+    telnet: TelnetFilter,
+}
+
+/// Strips telnet commands out of the input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum TelnetFilter {
+    #[default]
+    Data,
+    Command,
+    Option,
+    Subnegotiation,
+    SubnegotiationCommand,
+}
+
+impl TelnetFilter {
+    fn filter(&mut self, bytes: &[u8]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(bytes.len());
+        for &byte in bytes {
+            *self = match (*self, byte) {
+                (TelnetFilter::Data, 255) => TelnetFilter::Command,
+                (TelnetFilter::Data, _) => {
+                    data.push(byte);
+                    TelnetFilter::Data
+                }
+                (TelnetFilter::Command, 255) => {
+                    data.push(255);
+                    TelnetFilter::Data
+                }
+                (TelnetFilter::Command, 250) => TelnetFilter::Subnegotiation,
+                (TelnetFilter::Command, 251..=254) => TelnetFilter::Option,
+                (TelnetFilter::Command, _) | (TelnetFilter::Option, _) => TelnetFilter::Data,
+                (TelnetFilter::Subnegotiation, 255) => TelnetFilter::SubnegotiationCommand,
+                (TelnetFilter::Subnegotiation, _) => TelnetFilter::Subnegotiation,
+                (TelnetFilter::SubnegotiationCommand, 240) => TelnetFilter::Data,
+                (TelnetFilter::SubnegotiationCommand, _) => TelnetFilter::Subnegotiation,
+            };
+        }
+        data
+    }
+}
+
+impl RemoteClient {
+    fn send(&mut self, data: &[u8]) {
+        self.backlog.extend_from_slice(data);
+    }
+
+    /// Writes as much of the backlog as the socket takes, false once the client is gone.
+    fn flush(&mut self) -> bool {
+        while !self.backlog.is_empty() {
+            match self.stream.write(&self.backlog) {
+                Ok(0) => return false,
+                Ok(n) => {
+                    self.backlog.drain(..n);
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => return false,
+            }
+        }
+        self.backlog.len() <= MAX_BACKLOG
+    }
+
+    /// Reads what the client typed, None once it has hung up.
+    fn receive(&mut self, telnet: bool) -> Option<Vec<TermKeyEvent>> {
+        let mut bytes = Vec::new();
+        let mut buf = [0u8; 1024];
+        loop {
+            match self.stream.read(&mut buf) {
+                Ok(0) => return None,
+                Ok(n) => bytes.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                Err(_) => return None,
+            }
+        }
+        if telnet {
+            bytes = self.telnet.filter(&bytes);
+        }
+        Some(self.decoder.decode(&bytes))
+    }
+}
+
+/// Serves the terminal on the same entity to TCP clients, see the module docs.
+#[derive(Component, Debug)]
+pub struct TerminalServer {
+    /// Negotiate character mode with telnet clients, turn off for raw ANSI clients
+    pub telnet: bool,
+    listener: TcpListener,
+    clients: Vec<RemoteClient>,
+    next_id: u64,
+}
+
+impl TerminalServer {
+    /// Listens on the given address, use port 0 to pick a free port.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        listener.set_nonblocking(true)?;
+        Ok(TerminalServer {
+            telnet: true,
+            listener,
+            clients: Vec::new(),
+            next_id: 0,
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Ids of the clients connected right now.
+    pub fn clients(&self) -> impl Iterator<Item = u64> + '_ {
+        self.clients.iter().map(|client| client.id)
+    }
+
+    pub fn disconnect(&mut self, client: u64) {
+        self.clients.retain(|c| c.id != client);
+    }
+}
+
+/// Accepts new clients, streams the cells drawn this frame to everyone and turns what they typed
+/// into events. Runs before the draw list is used up by the cells.
+pub(crate) fn serve_terminals(
+    mut servers: Query<(Entity, &mut TerminalServer, &TerminalComponent, &TermStatus)>,
+    mut input_events: EventWriter<RemoteInput>,
+) {
+    for (entity, mut server, termy, status) in servers.iter_mut() {
+        if !status.ready() {
+            continue;
+        }
+        let server = &mut *server;
+        let termy_backend = termy.ratatui_terminal.backend();
+        let cursor = if termy_backend.cursor {
+            let (x, y) = termy_backend.cursor_pos;
+            format!("\x1b[?25h\x1b[{};{}H", y + 1, x + 1)
+        } else {
+            "\x1b[?25l".to_string()
+        };
+
+        while let Ok((stream, _)) = server.listener.accept() {
+            if stream.set_nonblocking(true).is_err() {
+                continue;
+            }
+            let mut client = RemoteClient {
+                id: server.next_id,
+                stream,
+                backlog: Vec::new(),
+                decoder: InputDecoder::new(),
+                telnet: TelnetFilter::default(),
+            };
+            server.next_id += 1;
+            if server.telnet {
+                client.send(TELNET_CHARACTER_MODE);
+            }
+            // newcomers get the whole screen, this frame's draws are already in it
+            client.send(b"\x1b[0m\x1b[H\x1b[2J");
+            client.send(ansi_batch(&screen_cells(&termy_backend.buffer)).as_bytes());
+            client.send(cursor.as_bytes());
+            // hung up already, or can't even take the first screen
+            if !client.flush() {
+                continue;
+            }
+            server.clients.push(client);
+        }
+
+        let update = if termy_backend.vcupdate.is_empty() {
+            None
+        } else {
+            Some(ansi_batch(&termy_backend.vcupdate) + &cursor)
+        };
+
+        let telnet = server.telnet;
+        server.clients.retain_mut(|client| {
+            if let Some(update) = &update {
+                client.send(update.as_bytes());
+            }
+            let Some(keys) = client.receive(telnet) else {
+                return false;
+            };
+            input_events.send_batch(keys.into_iter().map(|key| RemoteInput {
+                terminal: entity,
+                client: client.id,
+                key,
+            }));
+            client.flush()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TermKey;
+
+    #[test]
+    fn data_passes_through() {
+        let mut telnet = TelnetFilter::default();
+        assert_eq!(telnet.filter(b"ls -l\r\0"), b"ls -l\r\0");
+    }
+
+    #[test]
+    fn commands_are_stripped() {
+        let mut telnet = TelnetFilter::default();
+        // IAC WILL ECHO, IAC NOP, IAC DO SUPPRESS-GO-AHEAD around the typed bytes
+        assert_eq!(
+            telnet.filter(&[b'a', 255, 251, 1, 255, 241, b'b', 255, 253, 3, b'c']),
+            b"abc"
+        );
+    }
+
+    #[test]
+    fn escaped_iac_is_data() {
+        let mut telnet = TelnetFilter::default();
+        assert_eq!(telnet.filter(&[b'x', 255, 255, b'y']), [b'x', 255, b'y']);
+    }
+
+    #[test]
+    fn subnegotiation_is_stripped() {
+        let mut telnet = TelnetFilter::default();
+        // NAWS 80x24, with a doubled IAC inside
+        assert_eq!(
+            telnet.filter(&[b'a', 255, 250, 31, 0, 80, 255, 255, 0, 24, 255, 240, b'b']),
+            b"ab"
+        );
+    }
+
+    #[test]
+    fn commands_split_across_reads() {
+        let mut telnet = TelnetFilter::default();
+        let mut data = Vec::new();
+        for chunk in [
+            &[b'a', 255][..],
+            &[251],
+            &[1, b'b', 255, 250],
+            &[24, 255],
+            &[240, b'c'],
+        ] {
+            data.extend(telnet.filter(chunk));
+        }
+        assert_eq!(data, b"abc");
+        assert_eq!(telnet, TelnetFilter::Data);
+    }
+
+    #[test]
+    fn filtered_input_decodes_to_keys() {
+        let mut telnet = TelnetFilter::default();
+        let mut decoder = InputDecoder::new();
+        let mut keys = Vec::new();
+        for chunk in [&[255, 251, 1, 0x1b, b'['][..], b"B\r", &[0, 255, 241]] {
+            keys.extend(decoder.decode(&telnet.filter(chunk)));
+        }
+        assert_eq!(
+            keys,
+            [
+                TermKeyEvent::new(TermKey::Down),
+                TermKeyEvent::new(TermKey::Enter),
+            ]
+        );
+    }
+}
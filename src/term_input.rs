@@ -0,0 +1,471 @@
+//! Key presses as a terminal sees them, shaped after crossterm's key events so ratatui apps can
+//! handle them the way they are used to. Raw terminal input bytes from remote clients are decoded
+//! into them, and so are Bevy key presses. The events apps get are here too, so the drivers don't
+//! depend on each other for them.
+
+use bevy::{
+    ecs::{entity::Entity, event::Event},
+    input::keyboard::Key,
+};
+
+/// A key, without modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TermKey {
+    Char(char),
+    Enter,
+    Backspace,
+    Tab,
+    BackTab,
+    Esc,
+    Up,
+    Down,
+    Left,
+    Right,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Insert,
+    Delete,
+    /// Function key, 1 to 12
+    F(u8),
+}
+
+/// A key press with the modifiers held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TermKeyEvent {
+    pub key: TermKey,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl TermKeyEvent {
+    pub fn new(key: TermKey) -> Self {
+        TermKeyEvent {
+            key,
+            ctrl: false,
+            alt: false,
+        }
+    }
+
+    pub fn ctrl(key: TermKey) -> Self {
+        TermKeyEvent {
+            ctrl: true,
+            ..TermKeyEvent::new(key)
+        }
+    }
+
+    /// The terminal key for a Bevy key press, None for keys terminals don't have.
+    pub fn from_bevy(key: &Key, ctrl: bool, alt: bool) -> Option<Self> {
+        let key = match key {
+            Key::Character(text) => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => TermKey::Char(c),
+                    _ => return None,
+                }
+            }
+            Key::Space => TermKey::Char(' '),
+            Key::Enter => TermKey::Enter,
+            Key::Tab => TermKey::Tab,
+            Key::Backspace => TermKey::Backspace,
+            Key::Escape => TermKey::Esc,
+            Key::ArrowUp => TermKey::Up,
+            Key::ArrowDown => TermKey::Down,
+            Key::ArrowLeft => TermKey::Left,
+            Key::ArrowRight => TermKey::Right,
+            Key::Home => TermKey::Home,
+            Key::End => TermKey::End,
+            Key::PageUp => TermKey::PageUp,
+            Key::PageDown => TermKey::PageDown,
+            Key::Insert => TermKey::Insert,
+            Key::Delete => TermKey::Delete,
+            Key::F1 => TermKey::F(1),
+            Key::F2 => TermKey::F(2),
+            Key::F3 => TermKey::F(3),
+            Key::F4 => TermKey::F(4),
+            Key::F5 => TermKey::F(5),
+            Key::F6 => TermKey::F(6),
+            Key::F7 => TermKey::F(7),
+            Key::F8 => TermKey::F(8),
+            Key::F9 => TermKey::F(9),
+            Key::F10 => TermKey::F(10),
+            Key::F11 => TermKey::F(11),
+            Key::F12 => TermKey::F(12),
+            _ => return None,
+        };
+        Some(TermKeyEvent { key, ctrl, alt })
+    }
+
+    /// The bytes a terminal sends for the key, the reverse of [`InputDecoder`]. None for function
+    /// keys past F12.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        let sequence = |s: &str| s.as_bytes().to_vec();
+        let mut bytes = match self.key {
+            TermKey::Char(' ') if self.ctrl => vec![0],
+            // ctrl+letter and friends are the C0 control codes
+            TermKey::Char(c)
+                if self.ctrl && c.is_ascii() && ('@'..='_').contains(&c.to_ascii_uppercase()) =>
+            {
+                vec![c.to_ascii_uppercase() as u8 - b'@']
+            }
+            TermKey::Char(c) => c.to_string().into_bytes(),
+            TermKey::Enter => sequence("\r"),
+            TermKey::Backspace => sequence("\x7f"),
+            TermKey::Tab => sequence("\t"),
+            TermKey::BackTab => sequence("\x1b[Z"),
+            TermKey::Esc => sequence("\x1b"),
+            TermKey::Up => sequence("\x1b[A"),
+            TermKey::Down => sequence("\x1b[B"),
+            TermKey::Right => sequence("\x1b[C"),
+            TermKey::Left => sequence("\x1b[D"),
+            TermKey::Home => sequence("\x1b[H"),
+            TermKey::End => sequence("\x1b[F"),
+            TermKey::Insert => sequence("\x1b[2~"),
+            TermKey::Delete => sequence("\x1b[3~"),
+            TermKey::PageUp => sequence("\x1b[5~"),
+            TermKey::PageDown => sequence("\x1b[6~"),
+            TermKey::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+            // the numbers skip 16 and 22, like the decoder expects
+            TermKey::F(n @ 5..=12) => {
+                let code = [15, 17, 18, 19, 20, 21, 23, 24][n as usize - 5];
+                sequence(&format!("\x1b[{}~", code))
+            }
+            TermKey::F(_) => return None,
+        };
+        // alt is sent as an escape prefix
+        if self.alt {
+            bytes.insert(0, 0x1b);
+        }
+        Some(bytes)
+    }
+}
+
+/// Something that happened to the terminal, like crossterm's `Event`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TermEvent {
+    Key(TermKeyEvent),
+    /// The grid changed to this many columns and rows
+    Resize(u16, u16),
+}
+
+/// A key typed by a remote client of a [`crate::TerminalServer`].
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RemoteInput {
+    /// Entity of the terminal the client is watching
+    pub terminal: Entity,
+    pub client: u64,
+    pub key: TermKeyEvent,
+}
+
+/// Decodes the bytes a terminal sends for key presses, keeping incomplete sequences between
+/// chunks.
+#[derive(Debug, Clone, Default)]
+pub struct InputDecoder {
+    pending: Vec<u8>,
+    /// The last chunk ended with a CR, a NUL or LF starting the next one is part of the enter
+    after_cr: bool,
+}
+
+impl InputDecoder {
+    pub fn new() -> Self {
+        InputDecoder::default()
+    }
+
+    /// Decodes a chunk of input. An escape at the very end of a chunk is taken as the Esc key,
+    /// terminals send whole sequences in one write, but a sequence cut off after `ESC [` waits
+    /// for the rest like a cut off UTF-8 character does.
+    pub fn decode(&mut self, bytes: &[u8]) -> Vec<TermKeyEvent> {
+        self.pending.extend_from_slice(bytes);
+        let mut events = Vec::new();
+        let mut i = 0;
+        if self.after_cr && matches!(self.pending.first(), Some(0) | Some(b'\n')) {
+            i = 1;
+        }
+        self.after_cr = false;
+        while i < self.pending.len() {
+            match decode_one(&self.pending[i..]) {
+                Some((event, used)) => {
+                    events.extend(event);
+                    self.after_cr = self.pending[i..] == [b'\r'];
+                    i += used;
+                }
+                // the rest of a UTF-8 character or escape sequence is still on its way
+                None => break,
+            }
+        }
+        self.pending.drain(..i);
+        events
+    }
+}
+
+/// Decodes one key from the start of the input, returning it and the bytes it took. None when
+/// the input ends in the middle of a character or CSI sequence.
+fn decode_one(input: &[u8]) -> Option<(Option<TermKeyEvent>, usize)> {
+    let key = |key| Some(TermKeyEvent::new(key));
+    Some(match input[0] {
+        0x1b => match input.get(1) {
+            None => (key(TermKey::Esc), 1),
+            Some(b'[') => {
+                let sequence = &input[2..];
+                // parameter and intermediate bytes up to the final byte
+                match sequence.iter().position(|b| !(0x20..=0x3f).contains(b)) {
+                    None => return None,
+                    Some(end) if (0x40..=0x7e).contains(&sequence[end]) => {
+                        (decode_csi(&sequence[..=end]), end + 3)
+                    }
+                    // not a sequence after all, just escape and whatever follows
+                    Some(_) => (key(TermKey::Esc), 1),
+                }
+            }
+            Some(b'O') => match input.get(2) {
+                Some(c @ b'P'..=b'S') => (key(TermKey::F(c - b'P' + 1)), 3),
+                Some(c) => (arrow(*c).map(TermKeyEvent::new), 3),
+                None => (key(TermKey::Esc), 1),
+            },
+            Some(0x1b) => (key(TermKey::Esc), 1),
+            // escape before a key is alt
+            Some(_) => {
+                let (event, used) = decode_one(&input[1..])?;
+                (event.map(|e| TermKeyEvent { alt: true, ..e }), used + 1)
+            }
+        },
+        // telnet sends enter as CR NUL or CR LF
+        b'\r' => {
+            let used = if matches!(input.get(1), Some(0) | Some(b'\n')) {
+                2
+            } else {
+                1
+            };
+            (key(TermKey::Enter), used)
+        }
+        b'\n' => (key(TermKey::Enter), 1),
+        b'\t' => (key(TermKey::Tab), 1),
+        0x7f | 0x08 => (key(TermKey::Backspace), 1),
+        0 => (Some(TermKeyEvent::ctrl(TermKey::Char(' '))), 1),
+        c @ 1..=26 => (
+            Some(TermKeyEvent::ctrl(TermKey::Char((c - 1 + b'a') as char))),
+            1,
+        ),
+        c if c < 0x20 => (None, 1),
+        c => {
+            let len = match c {
+                0xc0..=0xdf => 2,
+                0xe0..=0xef => 3,
+                0xf0..=0xf7 => 4,
+                _ => 1,
+            };
+            let bytes = input.get(..len)?;
+            let event = std::str::from_utf8(bytes)
+                .ok()
+                .and_then(|s| s.chars().next())
+                .map(|c| TermKeyEvent::new(TermKey::Char(c)));
+            (event, len)
+        }
+    })
+}
+
+fn arrow(c: u8) -> Option<TermKey> {
+    match c {
+        b'A' => Some(TermKey::Up),
+        b'B' => Some(TermKey::Down),
+        b'C' => Some(TermKey::Right),
+        b'D' => Some(TermKey::Left),
+        b'H' => Some(TermKey::Home),
+        b'F' => Some(TermKey::End),
+        _ => None,
+    }
+}
+
+/// Decodes a complete sequence that followed `ESC [`, ending with its final byte.
+fn decode_csi(input: &[u8]) -> Option<TermKeyEvent> {
+    let end = input.len() - 1;
+    let params = std::str::from_utf8(&input[..end]).ok()?;
+    let mut numbers = params.split(';').map(|p| p.parse::<u8>().unwrap_or(0));
+    let first = numbers.next().unwrap_or(0);
+    // xterm puts modifiers in the second parameter, 1 + shift 1 + alt 2 + ctrl 4
+    let modifiers = numbers.next().unwrap_or(1).saturating_sub(1);
+
+    let key = match input[end] {
+        b'Z' => Some(TermKey::BackTab),
+        b'~' => match first {
+            1 | 7 => Some(TermKey::Home),
+            2 => Some(TermKey::Insert),
+            3 => Some(TermKey::Delete),
+            4 | 8 => Some(TermKey::End),
+            5 => Some(TermKey::PageUp),
+            6 => Some(TermKey::PageDown),
+            11..=15 => Some(TermKey::F(first - 10)),
+            17..=21 => Some(TermKey::F(first - 11)),
+            23 | 24 => Some(TermKey::F(first - 12)),
+            _ => None,
+        },
+        c @ b'P'..=b'S' => Some(TermKey::F(c - b'P' + 1)),
+        c => arrow(c),
+    };
+    key.map(|key| TermKeyEvent {
+        key,
+        ctrl: modifiers & 4 != 0,
+        alt: modifiers & 2 != 0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(chunks: &[&[u8]]) -> Vec<TermKeyEvent> {
+        let mut decoder = InputDecoder::new();
+        chunks
+            .iter()
+            .flat_map(|chunk| decoder.decode(chunk))
+            .collect()
+    }
+
+    fn alt(key: TermKey) -> TermKeyEvent {
+        TermKeyEvent {
+            alt: true,
+            ..TermKeyEvent::new(key)
+        }
+    }
+
+    #[test]
+    fn plain_keys() {
+        assert_eq!(
+            decode(&[b"a\t\x7f\x1b"]),
+            [
+                TermKeyEvent::new(TermKey::Char('a')),
+                TermKeyEvent::new(TermKey::Tab),
+                TermKeyEvent::new(TermKey::Backspace),
+                TermKeyEvent::new(TermKey::Esc),
+            ]
+        );
+    }
+
+    #[test]
+    fn enter_variants() {
+        let enter = TermKeyEvent::new(TermKey::Enter);
+        // telnet's CR NUL and CR LF are one enter each
+        assert_eq!(decode(&[b"\r\0\r\n\r\n"]), [enter, enter, enter]);
+    }
+
+    #[test]
+    fn sequences_split_across_reads() {
+        let up = TermKeyEvent::new(TermKey::Up);
+        assert_eq!(decode(&[b"\x1b[", b"A"]), [up]);
+        assert_eq!(
+            decode(&[b"\x1b[1;", b"5", b"D"]),
+            [TermKeyEvent::ctrl(TermKey::Left)]
+        );
+        assert_eq!(
+            decode(&[b"\x1b[1", b"5~x"]),
+            [
+                TermKeyEvent::new(TermKey::F(5)),
+                TermKeyEvent::new(TermKey::Char('x')),
+            ]
+        );
+        assert_eq!(
+            decode(&["\u{e9}".as_bytes()[..1].as_ref(), &"\u{e9}".as_bytes()[1..]]),
+            [TermKeyEvent::new(TermKey::Char('\u{e9}'))]
+        );
+    }
+
+    #[test]
+    fn escape_without_a_sequence() {
+        // a control byte can't be part of a CSI sequence, so the escape was a key of its own
+        assert_eq!(
+            decode(&[b"\x1b[\r"]),
+            [
+                TermKeyEvent::new(TermKey::Esc),
+                TermKeyEvent::new(TermKey::Char('[')),
+                TermKeyEvent::new(TermKey::Enter),
+            ]
+        );
+    }
+
+    #[test]
+    fn modifiers() {
+        assert_eq!(
+            decode(&[b"\x03\x1ba\x1b[1;3C\x1b[3;7~\x1b[Z"]),
+            [
+                TermKeyEvent::ctrl(TermKey::Char('c')),
+                alt(TermKey::Char('a')),
+                alt(TermKey::Right),
+                TermKeyEvent {
+                    ctrl: true,
+                    ..alt(TermKey::Delete)
+                },
+                TermKeyEvent::new(TermKey::BackTab),
+            ]
+        );
+    }
+
+    #[test]
+    fn ss3_keys() {
+        assert_eq!(
+            decode(&[b"\x1bOP\x1bOA"]),
+            [
+                TermKeyEvent::new(TermKey::F(1)),
+                TermKeyEvent::new(TermKey::Up),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_sequences_are_dropped() {
+        assert_eq!(
+            decode(&[b"\x1b[200~x"]),
+            [TermKeyEvent::new(TermKey::Char('x'))]
+        );
+    }
+
+    #[test]
+    fn encoded_keys_decode_to_themselves() {
+        let mut events: Vec<_> = [
+            TermKey::Enter,
+            TermKey::Backspace,
+            TermKey::Tab,
+            TermKey::BackTab,
+            TermKey::Esc,
+            TermKey::Up,
+            TermKey::Down,
+            TermKey::Left,
+            TermKey::Right,
+            TermKey::Home,
+            TermKey::End,
+            TermKey::PageUp,
+            TermKey::PageDown,
+            TermKey::Insert,
+            TermKey::Delete,
+            TermKey::Char('x'),
+            TermKey::Char('\u{e9}'),
+        ]
+        .into_iter()
+        .chain((1..=12).map(TermKey::F))
+        .map(TermKeyEvent::new)
+        .collect();
+        events.extend([
+            TermKeyEvent::ctrl(TermKey::Char('c')),
+            TermKeyEvent::ctrl(TermKey::Char(' ')),
+            alt(TermKey::Char('a')),
+        ]);
+
+        for event in events {
+            assert_eq!(decode(&[&event.to_bytes().unwrap()]), [event], "{event:?}");
+        }
+        assert_eq!(TermKeyEvent::new(TermKey::F(13)).to_bytes(), None);
+    }
+
+    #[test]
+    fn bevy_keys() {
+        assert_eq!(
+            TermKeyEvent::from_bevy(&Key::Character("q".into()), true, false),
+            Some(TermKeyEvent::ctrl(TermKey::Char('q')))
+        );
+        assert_eq!(
+            TermKeyEvent::from_bevy(&Key::F3, false, false),
+            Some(TermKeyEvent::new(TermKey::F(3)))
+        );
+        assert_eq!(TermKeyEvent::from_bevy(&Key::Shift, false, false), None);
+    }
+}
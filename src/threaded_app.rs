@@ -0,0 +1,471 @@
+//! Runs an existing blocking ratatui app loop on its own thread. The app gets a `Terminal` whose
+//! backend forwards every call to the [`BevyBackend`] of the terminal entity, and a
+//! [`TermEvents`] with crossterm style `poll` and `read` fed by the Bevy keyboard, so a loop like
+//!
+//! ```ignore
+//! loop {
+//!     terminal.draw(|f| ui(f, &app))?;
+//!     if events.poll(tick_rate)? {
+//!         if let TermEvent::Key(key) = events.read()? { .. }
+//!     }
+//! }
+//! ```
+//!
+//! runs as is. The app sees a broken pipe error from both once the terminal entity is gone.
+
+use std::{
+    io::{self, ErrorKind},
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc, Mutex,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use bevy::{
+    input::{keyboard::KeyboardInput, ButtonState},
+    prelude::*,
+};
+use ratatui::{
+    backend::{Backend, ClearType, WindowSize},
+    buffer::Cell,
+    layout::{Rect, Size},
+    Terminal,
+};
+
+use crate::{
+    components::TerminalComponent,
+    ratatui_plugin::TermStatus,
+    term_input::{TermEvent, TermKeyEvent},
+    BevyBackend,
+};
+
+/// A backend call made on the app thread, applied to the real backend on the next frame.
+#[derive(Debug)]
+enum BackendCall {
+    Draw(Vec<(u16, u16, Cell)>),
+    AppendLines(u16),
+    HideCursor,
+    ShowCursor,
+    SetCursor(u16, u16),
+    Clear,
+    ClearRegion(ClearType),
+    Flush,
+}
+
+fn disconnected() -> io::Error {
+    io::Error::new(ErrorKind::BrokenPipe, "the Bevy terminal is gone")
+}
+
+/// The backend the app thread draws with, a stand in for the [`BevyBackend`] on the main thread.
+#[derive(Debug)]
+pub struct ProxyBackend {
+    calls: Sender<BackendCall>,
+    /// Kept up to date by the main thread every frame
+    window: Arc<Mutex<WindowSize>>,
+    cursor_pos: (u16, u16),
+}
+
+impl ProxyBackend {
+    fn call(&self, call: BackendCall) -> io::Result<()> {
+        self.calls.send(call).map_err(|_| disconnected())
+    }
+
+    fn window(&self) -> WindowSize {
+        *self.window.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Backend for ProxyBackend {
+    fn draw<'a, I>(&mut self, content: I) -> io::Result<()>
+    where
+        I: Iterator<Item = (u16, u16, &'a Cell)>,
+    {
+        let cells: Vec<_> = content.map(|(x, y, cell)| (x, y, cell.clone())).collect();
+        self.call(BackendCall::Draw(cells))
+    }
+
+    fn append_lines(&mut self, n: u16) -> io::Result<()> {
+        self.call(BackendCall::AppendLines(n))
+    }
+
+    fn hide_cursor(&mut self) -> io::Result<()> {
+        self.call(BackendCall::HideCursor)
+    }
+
+    fn show_cursor(&mut self) -> io::Result<()> {
+        self.call(BackendCall::ShowCursor)
+    }
+
+    fn get_cursor(&mut self) -> io::Result<(u16, u16)> {
+        Ok(self.cursor_pos)
+    }
+
+    fn set_cursor(&mut self, x: u16, y: u16) -> io::Result<()> {
+        self.cursor_pos = (x, y);
+        self.call(BackendCall::SetCursor(x, y))
+    }
+
+    fn clear(&mut self) -> io::Result<()> {
+        self.call(BackendCall::Clear)
+    }
+
+    fn clear_region(&mut self, clear_type: ClearType) -> io::Result<()> {
+        self.call(BackendCall::ClearRegion(clear_type))
+    }
+
+    fn size(&self) -> io::Result<Rect> {
+        let Size { width, height } = self.window().columns_rows;
+        Ok(Rect::new(0, 0, width, height))
+    }
+
+    fn window_size(&mut self) -> io::Result<WindowSize> {
+        Ok(self.window())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.call(BackendCall::Flush)
+    }
+}
+
+/// Events for the app thread, with crossterm style `poll` and `read`.
+#[derive(Debug)]
+pub struct TermEvents {
+    receiver: Receiver<TermEvent>,
+    peeked: Option<TermEvent>,
+}
+
+impl TermEvents {
+    /// Waits up to `timeout` for an event, true if [`TermEvents::read`] will return right away.
+    pub fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        if self.peeked.is_some() {
+            return Ok(true);
+        }
+        match self.receiver.recv_timeout(timeout) {
+            Ok(event) => {
+                self.peeked = Some(event);
+                Ok(true)
+            }
+            Err(RecvTimeoutError::Timeout) => Ok(false),
+            Err(RecvTimeoutError::Disconnected) => Err(disconnected()),
+        }
+    }
+
+    /// Waits for the next event.
+    pub fn read(&mut self) -> io::Result<TermEvent> {
+        match self.peeked.take() {
+            Some(event) => Ok(event),
+            None => self.receiver.recv().map_err(|_| disconnected()),
+        }
+    }
+}
+
+type AppLoop = Box<dyn FnOnce(Terminal<ProxyBackend>, TermEvents) -> io::Result<()> + Send>;
+
+/// A blocking app loop driving the terminal on the same entity, see the module docs.
+#[derive(Component)]
+pub struct ThreadedApp {
+    /// Keyboard input is only sent to focused apps
+    pub focused: bool,
+    /// Set once the app loop has returned
+    pub result: Option<io::Result<()>>,
+    /// The loop until it is started, which waits for the terminal so it starts at the right size
+    app_loop: Mutex<Option<AppLoop>>,
+    calls: Option<Mutex<Receiver<BackendCall>>>,
+    events: Option<Sender<TermEvent>>,
+    window: Option<Arc<Mutex<WindowSize>>>,
+    thread: Option<JoinHandle<io::Result<()>>>,
+}
+
+impl ThreadedApp {
+    /// Runs the loop on a new thread once the component is on a terminal entity.
+    pub fn new(
+        app_loop: impl FnOnce(Terminal<ProxyBackend>, TermEvents) -> io::Result<()> + Send + 'static,
+    ) -> Self {
+        ThreadedApp {
+            focused: true,
+            result: None,
+            app_loop: Mutex::new(Some(Box::new(app_loop))),
+            calls: None,
+            events: None,
+            window: None,
+            thread: None,
+        }
+    }
+
+    /// Sends an event to the app as if it came from the terminal.
+    pub fn send(&self, event: TermEvent) {
+        if let Some(events) = &self.events {
+            let _ = events.send(event);
+        }
+    }
+
+    fn start(&mut self, window: WindowSize) -> io::Result<()> {
+        let Some(app_loop) = self.app_loop.get_mut().ok().and_then(|l| l.take()) else {
+            return Ok(());
+        };
+        let (call_sender, call_receiver) = mpsc::channel();
+        let (event_sender, event_receiver) = mpsc::channel();
+        let window = Arc::new(Mutex::new(window));
+        let backend = ProxyBackend {
+            calls: call_sender,
+            window: window.clone(),
+            cursor_pos: (0, 0),
+        };
+        let events = TermEvents {
+            receiver: event_receiver,
+            peeked: None,
+        };
+        self.thread = Some(
+            thread::Builder::new()
+                .name("ratatui app".to_string())
+                .spawn(move || app_loop(Terminal::new(backend)?, events))?,
+        );
+        self.calls = Some(Mutex::new(call_receiver));
+        self.events = Some(event_sender);
+        self.window = Some(window);
+        Ok(())
+    }
+
+    /// Applies the calls the app made since the last frame.
+    fn apply_calls(&self, backend: &mut BevyBackend) -> io::Result<()> {
+        let Some(calls) = &self.calls else {
+            return Ok(());
+        };
+        let Ok(calls) = calls.lock() else {
+            return Ok(());
+        };
+        for call in calls.try_iter() {
+            match call {
+                BackendCall::Draw(cells) => {
+                    backend.draw(cells.iter().map(|(x, y, cell)| (*x, *y, cell)))?
+                }
+                BackendCall::AppendLines(n) => backend.append_lines(n)?,
+                BackendCall::HideCursor => backend.hide_cursor()?,
+                BackendCall::ShowCursor => backend.show_cursor()?,
+                BackendCall::SetCursor(x, y) => backend.set_cursor(x, y)?,
+                BackendCall::Clear => backend.clear()?,
+                BackendCall::ClearRegion(clear_type) => backend.clear_region(clear_type)?,
+                BackendCall::Flush => backend.flush()?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Starts app loops, hands them the keyboard and resizes, and applies what they drew.
+pub(crate) fn update_threaded_apps(
+    mut apps: Query<(&mut ThreadedApp, &mut TerminalComponent, &TermStatus)>,
+    mut keyboard: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+) {
+    let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let alt = keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight]);
+    let typed: Vec<TermKeyEvent> = keyboard
+        .read()
+        .filter(|event| event.state == ButtonState::Pressed)
+        .filter_map(|event| TermKeyEvent::from_bevy(&event.logical_key, ctrl, alt))
+        .collect();
+
+    for (mut app, mut termy, status) in apps.iter_mut() {
+        let app = &mut *app;
+        if app.result.is_some() || !status.ready() {
+            continue;
+        }
+        let termy_backend = termy.ratatui_terminal.backend_mut();
+        let Ok(window) = termy_backend.window_size() else {
+            continue;
+        };
+
+        if app.thread.is_none() {
+            if let Err(e) = app.start(window) {
+                app.result = Some(Err(e));
+                continue;
+            }
+        }
+
+        if let Some(shared) = &app.window {
+            let mut shared = shared.lock().unwrap_or_else(|e| e.into_inner());
+            if shared.columns_rows != window.columns_rows {
+                let Size { width, height } = window.columns_rows;
+                app.send(TermEvent::Resize(width, height));
+            }
+            *shared = window;
+        }
+
+        if app.focused {
+            for key in &typed {
+                app.send(TermEvent::Key(*key));
+            }
+        }
+
+        if let Err(e) = app.apply_calls(termy_backend) {
+            app.result = Some(Err(e));
+            continue;
+        }
+
+        if app.thread.as_ref().is_some_and(|t| t.is_finished()) {
+            // draw whatever it did right before returning
+            let _ = app.apply_calls(termy_backend);
+            app.result = app.thread.take().map(|thread| {
+                thread
+                    .join()
+                    .unwrap_or_else(|_| Err(io::Error::other("the app thread panicked")))
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    fn backend(width: u16, height: u16) -> BevyBackend {
+        let mut backend = BevyBackend::default();
+        backend.resize(width, height);
+        backend
+    }
+
+    /// Starts the loop against `backend` and applies what it did once it returned.
+    fn run_to_end(
+        backend: &mut BevyBackend,
+        app_loop: impl FnOnce(Terminal<ProxyBackend>, TermEvents) -> io::Result<()> + Send + 'static,
+    ) -> ThreadedApp {
+        let mut app = ThreadedApp::new(app_loop);
+        app.start(backend.window_size().unwrap()).unwrap();
+        app.thread.take().unwrap().join().unwrap().unwrap();
+        app
+    }
+
+    fn cell(symbol: &str) -> Cell {
+        let mut cell = Cell::default();
+        cell.set_symbol(symbol);
+        cell
+    }
+
+    #[test]
+    fn calls_are_applied_in_order() {
+        let mut bevy_backend = backend(4, 1);
+        let app = run_to_end(&mut bevy_backend, |mut terminal, _| {
+            let proxy = terminal.backend_mut();
+            proxy.draw([(0, 0, &cell("a"))].into_iter())?;
+            proxy.clear()?;
+            proxy.draw([(1, 0, &cell("b"))].into_iter())?;
+            proxy.set_cursor(2, 0)?;
+            proxy.show_cursor()
+        });
+        app.apply_calls(&mut bevy_backend).unwrap();
+
+        assert_eq!(bevy_backend.snapshot_text(), " b\n");
+        assert_eq!(bevy_backend.cursor_pos, (2, 0));
+        assert!(bevy_backend.cursor);
+    }
+
+    #[test]
+    fn draw_queued_before_a_resize_is_clipped() {
+        let mut bevy_backend = backend(4, 2);
+        let app = run_to_end(&mut bevy_backend, |mut terminal, _| {
+            terminal
+                .backend_mut()
+                .draw([(0, 0, &cell("a")), (3, 1, &cell("z"))].into_iter())
+        });
+        bevy_backend.resize(2, 1);
+        app.apply_calls(&mut bevy_backend).unwrap();
+
+        assert_eq!(bevy_backend.snapshot_text(), "a\n");
+    }
+
+    #[test]
+    fn poll_and_read_time_out_and_disconnect() {
+        let (sender, receiver) = mpsc::channel();
+        let mut events = TermEvents {
+            receiver,
+            peeked: None,
+        };
+        assert!(!events.poll(Duration::from_millis(10)).unwrap());
+
+        sender.send(TermEvent::Resize(3, 2)).unwrap();
+        assert!(events.poll(Duration::from_millis(10)).unwrap());
+        // a polled event is kept for the read
+        assert!(events.poll(Duration::ZERO).unwrap());
+        assert!(matches!(events.read().unwrap(), TermEvent::Resize(3, 2)));
+
+        drop(sender);
+        let broken = |e: io::Error| e.kind() == ErrorKind::BrokenPipe;
+        assert!(events.poll(Duration::from_millis(10)).is_err_and(broken));
+        assert!(events.read().is_err_and(broken));
+    }
+
+    fn app_with(threaded: ThreadedApp, width: u16, height: u16) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_event::<KeyboardInput>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_systems(Update, update_threaded_apps);
+        let terminal = TerminalComponent {
+            ratatui_terminal: Terminal::new(backend(width, height)).unwrap(),
+        };
+        let entity = app
+            .world
+            .spawn((threaded, terminal, TermStatus::set_up()))
+            .id();
+        (app, entity)
+    }
+
+    /// Runs frames until the loop has returned.
+    fn result(app: &mut App, entity: Entity) -> io::Result<()> {
+        let start = Instant::now();
+        while start.elapsed() < Duration::from_secs(5) {
+            app.update();
+            if let Some(result) = app
+                .world
+                .get_mut::<ThreadedApp>(entity)
+                .unwrap()
+                .result
+                .take()
+            {
+                return result;
+            }
+            thread::sleep(Duration::from_millis(5));
+        }
+        panic!("the app loop never returned");
+    }
+
+    #[test]
+    fn grid_changes_are_sent_as_resize_events() {
+        let (sender, receiver) = mpsc::channel();
+        let (mut app, entity) = app_with(
+            ThreadedApp::new(move |_, mut events| {
+                let _ = sender.send(events.read()?);
+                Ok(())
+            }),
+            4,
+            2,
+        );
+        app.update();
+
+        app.world
+            .get_mut::<TerminalComponent>(entity)
+            .unwrap()
+            .ratatui_terminal
+            .backend_mut()
+            .resize(6, 3);
+        result(&mut app, entity).unwrap();
+
+        let event = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+        assert!(matches!(event, TermEvent::Resize(6, 3)));
+    }
+
+    #[test]
+    fn loop_result_and_panic_end_up_on_the_component() {
+        let (mut app, entity) =
+            app_with(ThreadedApp::new(|_, _| Err(io::Error::other("done"))), 4, 2);
+        assert_eq!(result(&mut app, entity).unwrap_err().to_string(), "done");
+
+        let (mut app, entity) = app_with(ThreadedApp::new(|_, _| panic!("app loop panic")), 4, 2);
+        let error = result(&mut app, entity).unwrap_err();
+        assert_eq!(error.to_string(), "the app thread panicked");
+    }
+}
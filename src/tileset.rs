@@ -0,0 +1,77 @@
+//! Tileset mode, where chosen cells are drawn as tiles of a sprite atlas instead of font glyphs.
+//! Cells are matched on their symbol, or on custom rules that also see the foreground colour, so
+//! a roguelike can draw `@` as the player sprite while widgets around it stay text.
+
+use std::{fmt, sync::Arc};
+
+use bevy::{prelude::*, utils::HashMap};
+use ratatui::style::Color as RatColor;
+
+type TileRule = Arc<dyn Fn(&str, RatColor) -> Option<usize> + Send + Sync>;
+
+/// Maps cell symbols to tiles of a texture atlas, set on a terminal with
+/// [`crate::BevyBackend::tileset`].
+#[derive(Clone)]
+pub struct Tileset {
+    pub image: Handle<Image>,
+    pub layout: Handle<TextureAtlasLayout>,
+    /// Tint tiles with the cell foreground, turn off for tiles that carry their own colours
+    pub tinted: bool,
+    symbols: HashMap<String, usize>,
+    rules: Vec<TileRule>,
+}
+
+impl Tileset {
+    /// Creates an empty tileset over the given atlas, tiles are tinted by default.
+    pub fn new(image: Handle<Image>, layout: Handle<TextureAtlasLayout>) -> Self {
+        Tileset {
+            image,
+            layout,
+            tinted: true,
+            symbols: HashMap::new(),
+            rules: Vec::new(),
+        }
+    }
+
+    /// Draws cells showing `symbol` with the tile at `index`.
+    pub fn map_symbol(mut self, symbol: &str, index: usize) -> Self {
+        self.symbols.insert(symbol.to_string(), index);
+        self
+    }
+
+    /// Adds a rule that picks a tile from the cell symbol and foreground colour. Rules are tried
+    /// in the order they were added, after the plain symbol mappings.
+    pub fn map_rule(
+        mut self,
+        rule: impl Fn(&str, RatColor) -> Option<usize> + Send + Sync + 'static,
+    ) -> Self {
+        self.rules.push(Arc::new(rule));
+        self
+    }
+
+    /// Whether tiles are tinted with the cell foreground.
+    pub fn tinted(mut self, value: bool) -> Self {
+        self.tinted = value;
+        self
+    }
+
+    /// The tile index a cell is drawn with, if it matches a symbol or a rule.
+    pub fn tile(&self, symbol: &str, fg: RatColor) -> Option<usize> {
+        self.symbols
+            .get(symbol)
+            .copied()
+            .or_else(|| self.rules.iter().find_map(|rule| rule(symbol, fg)))
+    }
+}
+
+impl fmt::Debug for Tileset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Tileset")
+            .field("image", &self.image)
+            .field("layout", &self.layout)
+            .field("tinted", &self.tinted)
+            .field("symbols", &self.symbols)
+            .field("rules", &self.rules.len())
+            .finish()
+    }
+}
@@ -0,0 +1,56 @@
+//! A headless app running the plugin without a window or renderer, for integration tests.
+
+#![allow(dead_code)]
+
+use bevy::{
+    app::ScheduleRunnerPlugin, asset::AssetPlugin, hierarchy::HierarchyPlugin, input::InputPlugin,
+    prelude::*, transform::TransformPlugin, window::WindowPlugin,
+};
+use bevy_ratatui::{BevyBackend, RatatuiPlugin, TerminalComponent};
+use ratatui::Terminal;
+
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins.set(ScheduleRunnerPlugin::run_once()),
+        AssetPlugin::default(),
+        WindowPlugin {
+            primary_window: None,
+            ..default()
+        },
+        InputPlugin,
+        HierarchyPlugin,
+        TransformPlugin,
+        RatatuiPlugin,
+    ))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlasLayout>()
+    .init_asset::<Font>();
+    app
+}
+
+/// A terminal of a fixed size that the plugin leaves the size of.
+pub fn terminal(width: u16, height: u16) -> TerminalComponent {
+    let mut backend = BevyBackend::default();
+    backend.manual_window_sizing(true);
+    backend.resize(width, height);
+    TerminalComponent {
+        ratatui_terminal: Terminal::new(backend).unwrap(),
+    }
+}
+
+/// Runs enough frames for new terminals to be set up.
+pub fn settle(app: &mut App) {
+    for _ in 0..5 {
+        app.update();
+    }
+}
+
+pub fn screen(app: &App, terminal: Entity) -> String {
+    app.world
+        .get::<TerminalComponent>(terminal)
+        .unwrap()
+        .ratatui_terminal
+        .backend()
+        .snapshot_text()
+}
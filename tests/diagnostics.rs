@@ -0,0 +1,28 @@
+mod common;
+
+use std::{thread, time::Duration};
+
+use bevy::diagnostic::DiagnosticsStore;
+use bevy_ratatui::TerminalDiagnosticsPlugin;
+
+use common::{headless_app, terminal};
+
+#[test]
+fn rebuild_time_covers_the_frames_of_the_rebuild() {
+    let mut app = headless_app();
+    app.add_plugins(TerminalDiagnosticsPlugin);
+    app.world.spawn(terminal(4, 2));
+
+    for _ in 0..6 {
+        app.update();
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    let store = app.world.resource::<DiagnosticsStore>();
+    let rebuild = store
+        .get(&TerminalDiagnosticsPlugin::REBUILD_TIME)
+        .and_then(|d| d.measurement())
+        .expect("a rebuild was measured");
+    // cleared on one frame, spawned on the next and timed on the one after
+    assert!(rebuild.value >= 20.0, "{}ms", rebuild.value);
+}
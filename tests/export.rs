@@ -0,0 +1,91 @@
+//! Golden tests for the buffer exports. The expected output lives in `tests/golden`, run with
+//! `BLESS=1` to rewrite it after an intended change and review the diff.
+
+use std::{fs, path::PathBuf};
+
+use bevy_ratatui::{export_ansi, export_html, export_text};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
+
+fn check(name: &str, actual: String) {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(name);
+    if std::env::var_os("BLESS").is_some() {
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+    let expected = fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("missing golden file {}: {e}", path.display()));
+    assert_eq!(actual, expected, "output differs from {}", path.display());
+}
+
+fn check_all(name: &str, buffer: &Buffer, area: Rect) {
+    check(&format!("{name}.txt"), export_text(buffer, area));
+    check(&format!("{name}.ans"), export_ansi(buffer, area));
+    check(&format!("{name}.html"), export_html(buffer, area));
+}
+
+fn styled() -> Buffer {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 2));
+    buffer.set_string(
+        0,
+        0,
+        "Hi",
+        Style::default()
+            .fg(Color::Red)
+            .bg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+    );
+    buffer.set_string(
+        3,
+        0,
+        "<&>",
+        Style::default()
+            .fg(Color::Rgb(1, 2, 3))
+            .add_modifier(Modifier::UNDERLINED | Modifier::CROSSED_OUT),
+    );
+    buffer.set_string(
+        0,
+        1,
+        "ok",
+        Style::default()
+            .fg(Color::Indexed(200))
+            .add_modifier(Modifier::ITALIC | Modifier::REVERSED),
+    );
+    buffer
+}
+
+fn wide() -> Buffer {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 6, 2));
+    buffer.set_string(0, 0, "世界a", Style::default().fg(Color::Green));
+    buffer.set_string(1, 1, "表", Style::default());
+    buffer
+}
+
+#[test]
+fn styled_buffer() {
+    let buffer = styled();
+    check_all("styled", &buffer, buffer.area);
+}
+
+#[test]
+fn wide_chars() {
+    let buffer = wide();
+    check_all("wide", &buffer, buffer.area);
+}
+
+#[test]
+fn empty_buffer() {
+    let buffer = Buffer::empty(Rect::new(0, 0, 3, 2));
+    check_all("empty", &buffer, buffer.area);
+}
+
+#[test]
+fn area_outside_the_buffer() {
+    let buffer = styled();
+    check_all("outside", &buffer, Rect::new(20, 20, 4, 4));
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ratatui::{RenderMode, TerminalComponent};
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+};
+
+use common::{headless_app, settle, terminal};
+
+fn draw(app: &mut App, terminal: Entity, line: Line<'static>) {
+    let mut termy = app.world.get_mut::<TerminalComponent>(terminal).unwrap();
+    termy
+        .ratatui_terminal
+        .draw(|frame| frame.render_widget(Paragraph::new(line), frame.size()))
+        .unwrap();
+}
+
+fn quads(app: &App, terminal: Entity) -> usize {
+    let backend = app
+        .world
+        .get::<TerminalComponent>(terminal)
+        .unwrap()
+        .ratatui_terminal
+        .backend();
+    let (background, _) = backend.row_entities[0];
+    app.world.get::<Children>(background).map_or(0, |c| c.len())
+}
+
+#[test]
+fn leftover_background_quads_are_despawned() {
+    let mut app = headless_app();
+    let mut termy = terminal(6, 1);
+    termy.ratatui_terminal.backend_mut().render_mode = RenderMode::Rows;
+    let terminal = app.world.spawn(termy).id();
+    settle(&mut app);
+
+    let runs = Line::from(vec![
+        Span::styled("ab", Style::default().bg(Color::Red)),
+        Span::styled("cd", Style::default().bg(Color::Blue)),
+        Span::styled("ef", Style::default().bg(Color::Green)),
+    ]);
+    draw(&mut app, terminal, runs);
+    app.update();
+    app.update();
+    assert_eq!(quads(&app, terminal), 3);
+
+    draw(
+        &mut app,
+        terminal,
+        Line::styled("abcdef", Style::default().bg(Color::Red)),
+    );
+    app.update();
+    app.update();
+    assert_eq!(quads(&app, terminal), 1);
+}
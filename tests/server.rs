@@ -0,0 +1,72 @@
+mod common;
+
+use std::{
+    io::{ErrorKind, Read, Write},
+    net::TcpStream,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use bevy_ratatui::{RemoteInput, TermKey, TerminalComponent, TerminalServer};
+use ratatui::widgets::Paragraph;
+
+use common::{headless_app, settle, terminal};
+
+/// Everything the server sent that has arrived by now.
+fn received(stream: &mut TcpStream) -> String {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    loop {
+        match stream.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => data.extend_from_slice(&buf[..n]),
+            Err(e) if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => break,
+            Err(e) => panic!("{}", e),
+        }
+    }
+    String::from_utf8(data).unwrap()
+}
+
+#[test]
+fn loopback_client_gets_the_screen_then_diffs_and_sends_keys() {
+    let mut app = headless_app();
+    let mut server = TerminalServer::bind("127.0.0.1:0").unwrap();
+    server.telnet = false;
+    let addr = server.local_addr().unwrap();
+    let terminal = app.world.spawn((terminal(4, 1), server)).id();
+    settle(&mut app);
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream
+        .set_read_timeout(Some(Duration::from_millis(100)))
+        .unwrap();
+    app.update();
+    let screen = received(&mut stream);
+    assert!(screen.starts_with("\x1b[0m\x1b[H\x1b[2J"), "{:?}", screen);
+
+    app.world
+        .get_mut::<TerminalComponent>(terminal)
+        .unwrap()
+        .ratatui_terminal
+        .draw(|frame| frame.render_widget(Paragraph::new("hi"), frame.size()))
+        .unwrap();
+    app.update();
+    let diff = received(&mut stream);
+    assert!(diff.contains("hi"), "{:?}", diff);
+    assert!(!diff.contains("\x1b[2J"), "{:?}", diff);
+
+    stream.write_all(b"\x1b[A").unwrap();
+    let mut keys = Vec::new();
+    for _ in 0..20 {
+        app.update();
+        let events = app.world.resource::<Events<RemoteInput>>();
+        keys.extend(events.iter_current_update_events().copied());
+        if !keys.is_empty() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].terminal, terminal);
+    assert_eq!(keys[0].key.key, TermKey::Up);
+}
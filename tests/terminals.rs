@@ -0,0 +1,114 @@
+mod common;
+
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_ratatui::{
+    CellComponent, ContextError, DefaultTerminal, RatWidget, RatatuiContext, TerminalComponent,
+    TerminalName,
+};
+use ratatui::{layout::Rect, widgets::Paragraph};
+
+use common::{headless_app, screen, settle, terminal};
+
+fn cells(app: &App, terminal: Entity) -> Vec<Entity> {
+    let backend = app
+        .world
+        .get::<TerminalComponent>(terminal)
+        .unwrap()
+        .ratatui_terminal
+        .backend();
+    (0..backend.height)
+        .flat_map(|y| (0..backend.width).map(move |x| (x, y)))
+        .filter_map(|(x, y)| backend.cell_entity(x, y))
+        .collect()
+}
+
+fn symbols(app: &App, cells: &[Entity]) -> String {
+    cells
+        .iter()
+        .map(|cell| app.world.get::<CellComponent>(*cell).unwrap().cell.symbol())
+        .collect()
+}
+
+#[test]
+fn second_terminal_is_set_up_while_the_first_runs() {
+    let mut app = headless_app();
+    let first = app.world.spawn(terminal(3, 1)).id();
+    settle(&mut app);
+    app.world
+        .spawn(RatWidget::new(Rect::new(0, 0, 3, 1), Paragraph::new("one")))
+        .set_parent(first);
+    app.update();
+    let first_cells = cells(&app, first);
+    assert_eq!(first_cells.len(), 3);
+
+    let second = app.world.spawn(terminal(3, 2)).id();
+    app.world
+        .spawn(RatWidget::new(Rect::new(0, 0, 3, 2), Paragraph::new("two")))
+        .set_parent(second);
+    settle(&mut app);
+
+    // the first terminal kept its cells and the second got its own
+    assert_eq!(cells(&app, first), first_cells);
+    assert_eq!(symbols(&app, &first_cells), "one");
+    let second_cells = cells(&app, second);
+    assert_eq!(second_cells.len(), 6);
+    assert_eq!(symbols(&app, &second_cells), "two   ");
+    assert_eq!(screen(&app, first), "one\n");
+    assert_eq!(screen(&app, second), "two\n\n");
+}
+
+#[test]
+fn despawning_one_terminal_leaves_the_other_running() {
+    let mut app = headless_app();
+    let first = app.world.spawn(terminal(3, 1)).id();
+    let second = app.world.spawn(terminal(3, 1)).id();
+    settle(&mut app);
+
+    app.world.entity_mut(first).despawn_recursive();
+    app.world
+        .spawn(RatWidget::new(Rect::new(0, 0, 3, 1), Paragraph::new("two")))
+        .set_parent(second);
+    app.update();
+    app.update();
+
+    assert_eq!(symbols(&app, &cells(&app, second)), "two");
+}
+
+fn draw_by_name(mut context: RatatuiContext) {
+    context
+        .draw(|frame| frame.render_widget(Paragraph::new("def"), frame.size()))
+        .unwrap();
+    context
+        .named("log")
+        .unwrap()
+        .draw(|frame| frame.render_widget(Paragraph::new("log"), frame.size()))
+        .unwrap();
+}
+
+#[test]
+fn context_draws_to_the_default_and_named_terminals() {
+    let mut app = headless_app();
+    let default = app.world.spawn((terminal(3, 1), DefaultTerminal)).id();
+    let named = app
+        .world
+        .spawn((terminal(3, 1), TerminalName::new("log")))
+        .id();
+    app.add_systems(Update, draw_by_name);
+    settle(&mut app);
+
+    assert_eq!(symbols(&app, &cells(&app, default)), "def");
+    assert_eq!(symbols(&app, &cells(&app, named)), "log");
+}
+
+#[test]
+fn context_needs_a_default_among_several_terminals() {
+    let mut app = headless_app();
+    app.world.spawn(terminal(3, 1));
+    app.world.spawn((terminal(3, 1), TerminalName::new("log")));
+    settle(&mut app);
+
+    let mut state = SystemState::<RatatuiContext>::new(&mut app.world);
+    let mut context = state.get_mut(&mut app.world);
+    assert!(matches!(context.terminal(), Err(ContextError::NoDefault)));
+    assert!(context.named("log").is_ok());
+}
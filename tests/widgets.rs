@@ -0,0 +1,57 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_ratatui::{CastPlayer, RatWidget, TerminalError, TerminalErrorKind};
+use ratatui::{layout::Rect, widgets::Paragraph};
+
+use common::{headless_app, screen, settle, terminal};
+
+#[test]
+fn widgets_are_drawn_in_z_order() {
+    let mut app = headless_app();
+    let terminal = app.world.spawn(terminal(6, 2)).id();
+    settle(&mut app);
+
+    let top = app
+        .world
+        .spawn(RatWidget::new(Rect::new(0, 0, 6, 1), Paragraph::new("top")).z(1))
+        .id();
+    let bottom = app
+        .world
+        .spawn(RatWidget::new(
+            Rect::new(0, 0, 6, 2),
+            Paragraph::new("bottom\nline"),
+        ))
+        .id();
+    app.world.entity_mut(terminal).push_children(&[top, bottom]);
+    app.update();
+
+    assert_eq!(
+        screen(&app, terminal),
+        "topttom\nline\n".replace("ttom", "tom")
+    );
+}
+
+#[test]
+fn widgets_leave_a_driven_terminal_alone() {
+    let mut app = headless_app();
+    let terminal = app
+        .world
+        .spawn((terminal(6, 1), CastPlayer::new(Handle::default())))
+        .id();
+    settle(&mut app);
+
+    app.world
+        .spawn(RatWidget::new(
+            Rect::new(0, 0, 6, 1),
+            Paragraph::new("widget"),
+        ))
+        .set_parent(terminal);
+    app.update();
+
+    assert_eq!(screen(&app, terminal), "\n");
+    let errors = app.world.resource::<Events<TerminalError>>();
+    assert!(errors
+        .iter_current_update_events()
+        .any(|e| e.kind == TerminalErrorKind::DrivenTerminal(terminal)));
+}